@@ -0,0 +1,266 @@
+//! Architecture.
+//!
+//! This module contains the architectures java packages are built for and their names as used by the vendors.
+
+#[cfg(any(
+    feature = "azul",
+    feature = "corretto",
+    feature = "disco",
+    feature = "eclipse",
+    feature = "liberica",
+    feature = "microsoft",
+    feature = "sapmachine",
+    feature = "semeru"
+))]
+use crate::vendor::Vendor;
+use std::env;
+use std::fmt;
+
+// The names of the architectures as used by Azul.
+#[cfg(feature = "azul")]
+#[doc(hidden)]
+const AZUL_NAMES: &[(Arch, &str)] = &[(Arch::X86, "i686"), (Arch::X64, "x64"), (Arch::Aarch64, "aarch64"), (Arch::Arm32, "aarch32hf")];
+
+// The names of the architectures as used by Amazon (Corretto).
+#[cfg(feature = "corretto")]
+#[doc(hidden)]
+const CORRETTO_NAMES: &[(Arch, &str)] = &[(Arch::X86, "x86"), (Arch::X64, "x64"), (Arch::Aarch64, "aarch64"), (Arch::Arm32, "arm")];
+
+// The names of the architectures as used by foojay (Disco API).
+#[cfg(feature = "disco")]
+#[doc(hidden)]
+const DISCO_NAMES: &[(Arch, &str)] = &[
+    (Arch::X86, "x86"),
+    (Arch::X64, "x64"),
+    (Arch::Aarch64, "aarch64"),
+    (Arch::Arm32, "arm"),
+    (Arch::Ppc64le, "ppc64le"),
+    (Arch::S390x, "s390x"),
+    (Arch::Riscv64, "riscv64"),
+];
+
+// The names of the architectures as used by Eclipse (Adoptium).
+#[cfg(feature = "eclipse")]
+#[doc(hidden)]
+const ECLIPSE_NAMES: &[(Arch, &str)] = &[
+    (Arch::X86, "x32"),
+    (Arch::X64, "x64"),
+    (Arch::Aarch64, "aarch64"),
+    (Arch::Arm32, "arm"),
+    (Arch::Ppc64le, "ppc64le"),
+    (Arch::S390x, "s390x"),
+    (Arch::Riscv64, "riscv64"),
+];
+
+// The names of the architectures as used by BellSoft (Liberica), the bitness is passed separately.
+#[cfg(feature = "liberica")]
+#[doc(hidden)]
+const LIBERICA_NAMES: &[(Arch, &str)] = &[
+    (Arch::X86, "x86"),
+    (Arch::X64, "x86"),
+    (Arch::Aarch64, "arm"),
+    (Arch::Arm32, "arm"),
+    (Arch::Ppc64le, "ppc"),
+    (Arch::Riscv64, "riscv"),
+];
+
+// The names of the architectures as used by Microsoft.
+#[cfg(feature = "microsoft")]
+#[doc(hidden)]
+const MICROSOFT_NAMES: &[(Arch, &str)] = &[
+    (Arch::X64, "x64"), //
+    (Arch::Aarch64, "aarch64"),
+];
+
+// The names of the architectures as used by SAP (SapMachine).
+#[cfg(feature = "sapmachine")]
+#[doc(hidden)]
+const SAPMACHINE_NAMES: &[(Arch, &str)] = &[
+    (Arch::X64, "x64"), //
+    (Arch::Aarch64, "aarch64"),
+    (Arch::Ppc64le, "ppc64le"),
+];
+
+// The names of the architectures as used by IBM (Semeru).
+#[cfg(feature = "semeru")]
+#[doc(hidden)]
+const SEMERU_NAMES: &[(Arch, &str)] = &[
+    (Arch::X86, "x86-32"),
+    (Arch::X64, "x64"),
+    (Arch::Aarch64, "aarch64"),
+    (Arch::Ppc64le, "ppc64le"),
+    (Arch::S390x, "s390x"),
+];
+
+/// Enumeration of the supported architectures.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) enum Arch {
+    /// ARM 64-bit
+    Aarch64,
+    /// ARM 32-bit (hard-float)
+    Arm32,
+    /// PowerPC 64-bit (little endian)
+    Ppc64le,
+    /// RISC-V 64-bit
+    Riscv64,
+    /// IBM Z 64-bit
+    S390x,
+    /// Intel 64-bit
+    X64,
+    /// Intel 32-bit
+    X86,
+}
+
+impl Arch {
+    /// Returns the architecture of the host, if supported.
+    pub(crate) fn host() -> Option<Self> {
+        match env::consts::ARCH {
+            // Rust doesn't distinguish the endianness in the name
+            "powerpc64" if cfg!(target_endian = "little") => Some(Self::Ppc64le),
+            arch => Self::try_from(arch).ok(),
+        }
+    }
+
+    /// Returns the (canonical) id of the architecture.
+    pub(crate) fn id(&self) -> &'static str {
+        match self {
+            Self::Aarch64 => "aarch64",
+            Self::Arm32 => "arm32",
+            Self::Ppc64le => "ppc64le",
+            Self::Riscv64 => "riscv64",
+            Self::S390x => "s390x",
+            Self::X64 => "x64",
+            Self::X86 => "x86",
+        }
+    }
+
+    /// Returns the bitness (32 or 64) of the architecture.
+    #[cfg(feature = "liberica")]
+    pub(crate) fn bits(&self) -> &'static str {
+        match self {
+            Self::Arm32 | Self::X86 => "32",
+            _ => "64",
+        }
+    }
+
+    /// Returns the name of the architecture as used by the given vendor or `None`, if the vendor doesn't provide
+    /// builds for it.
+    ///
+    /// Vendors without a translation table (e.g. manifests) use the id of the architecture.
+    #[cfg(any(
+        feature = "azul",
+        feature = "corretto",
+        feature = "disco",
+        feature = "eclipse",
+        feature = "liberica",
+        feature = "microsoft",
+        feature = "sapmachine",
+        feature = "semeru"
+    ))]
+    pub(crate) fn name(&self, vendor: Vendor) -> Option<&'static str> {
+        let names: &[(Arch, &str)] = match vendor {
+            #[cfg(feature = "azul")]
+            Vendor::Azul => AZUL_NAMES,
+            #[cfg(feature = "corretto")]
+            Vendor::Corretto => CORRETTO_NAMES,
+            #[cfg(feature = "direct")]
+            Vendor::Direct => &[],
+            #[cfg(feature = "disco")]
+            Vendor::Disco => DISCO_NAMES,
+            #[cfg(feature = "eclipse")]
+            Vendor::Eclipse => ECLIPSE_NAMES,
+            #[cfg(feature = "github-release")]
+            Vendor::GithubRelease => &[],
+            #[cfg(feature = "liberica")]
+            Vendor::Liberica => LIBERICA_NAMES,
+            #[cfg(feature = "manifest")]
+            Vendor::Manifest => &[],
+            #[cfg(feature = "microsoft")]
+            Vendor::Microsoft => MICROSOFT_NAMES,
+            #[cfg(feature = "sapmachine")]
+            Vendor::SapMachine => SAPMACHINE_NAMES,
+            #[cfg(feature = "semeru")]
+            Vendor::Semeru => SEMERU_NAMES,
+            #[expect(unreachable_patterns)]
+            _ => unreachable!(),
+        };
+        if names.is_empty() {
+            return Some(self.id());
+        }
+
+        names.iter().find(|(arch, _)| arch == self).map(|(_, name)| *name)
+    }
+}
+
+impl fmt::Display for Arch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.id())
+    }
+}
+
+impl TryFrom<&str> for Arch {
+    type Error = &'static str;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let value = value.trim().to_lowercase();
+        match value.as_str() {
+            "aarch64" | "arm64" => Ok(Self::Aarch64),
+            "aarch32" | "aarch32hf" | "arm" | "arm32" | "armhf" | "armv7" | "armv7l" => Ok(Self::Arm32),
+            "powerpc64le" | "ppc64le" => Ok(Self::Ppc64le),
+            "riscv64" | "riscv64gc" => Ok(Self::Riscv64),
+            "s390x" => Ok(Self::S390x),
+            "amd64" | "x64" | "x86-64" | "x86_64" => Ok(Self::X64),
+            "i386" | "i486" | "i586" | "i686" | "x32" | "x86" => Ok(Self::X86),
+            // the default architecture of an installation is the one of the host as named by Rust
+            arch if arch == env::consts::ARCH => Self::host().ok_or("unsupported architecture"),
+            _ => Err("unsupported architecture"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn aliases() {
+        assert_eq!(Ok(Arch::X64), Arch::try_from("amd64"));
+        assert_eq!(Ok(Arch::X64), Arch::try_from("X86_64"));
+        assert_eq!(Ok(Arch::X86), Arch::try_from("i686"));
+        assert_eq!(Ok(Arch::X86), Arch::try_from("x32"));
+        assert_eq!(Ok(Arch::Aarch64), Arch::try_from("arm64"));
+        assert_eq!(Ok(Arch::Arm32), Arch::try_from("armv7l"));
+        assert_eq!(Ok(Arch::Ppc64le), Arch::try_from("ppc64le"));
+        assert_eq!(Ok(Arch::S390x), Arch::try_from("s390x"));
+        assert_eq!(Ok(Arch::Riscv64), Arch::try_from("riscv64"));
+        assert!(Arch::try_from("sparcv9").is_err());
+        assert!(Arch::try_from("").is_err());
+    }
+
+    #[test]
+    fn host() {
+        assert_eq!(Arch::host(), Arch::try_from(env::consts::ARCH).ok());
+    }
+
+    #[cfg(feature = "eclipse")]
+    #[test]
+    fn names_eclipse() {
+        assert_eq!(Some("x32"), Arch::X86.name(Vendor::Eclipse));
+        assert_eq!(Some("x64"), Arch::X64.name(Vendor::Eclipse));
+        assert_eq!(Some("riscv64"), Arch::Riscv64.name(Vendor::Eclipse));
+    }
+
+    #[cfg(feature = "microsoft")]
+    #[test]
+    fn names_unsupported() {
+        assert_eq!(None, Arch::S390x.name(Vendor::Microsoft));
+    }
+
+    #[cfg(all(feature = "eclipse", feature = "manifest"))]
+    #[test]
+    fn names_without_table() {
+        assert_eq!(Some("arm32"), Arch::Arm32.name(Vendor::Manifest));
+    }
+}
@@ -0,0 +1,295 @@
+//! Archive.
+//!
+//! This module contains the archive formats java packages are distributed in and the code to unpack them.
+
+use anyhow::anyhow;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+use tracing::{trace, warn};
+
+/// Enumeration of the supported archive formats.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub(crate) enum ArchiveFormat {
+    #[serde(rename = "tar.gz")]
+    TarGz,
+    #[serde(rename = "tar.xz")]
+    TarXz,
+    #[serde(rename = "zip")]
+    Zip,
+}
+
+impl ArchiveFormat {
+    /// Returns the archive format packages for the given operating system are usually distributed in.
+    pub(crate) fn for_os(os: &str) -> Self {
+        match os.trim().to_lowercase().as_str() {
+            "windows" => Self::Zip,
+            _ => Self::TarGz,
+        }
+    }
+
+    /// Detects the archive format from the file extension of the given URL (or filename).
+    pub(crate) fn from_url(url: &str) -> Option<Self> {
+        let path = Url::parse(url).map_or_else(|_| url.to_lowercase(), |url| url.path().to_lowercase());
+        if path.ends_with(".tar.gz") || path.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else if path.ends_with(".tar.xz") || path.ends_with(".txz") {
+            Some(Self::TarXz)
+        } else if path.ends_with(".zip") {
+            Some(Self::Zip)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the file extension (without leading dot) of the archive format.
+    pub(crate) fn ext(&self) -> &'static str {
+        match self {
+            Self::TarGz => "tar.gz",
+            Self::TarXz => "tar.xz",
+            Self::Zip => "zip",
+        }
+    }
+
+    /// Unpacks the given archive into the given directory, removing the first component of each entry.
+    #[tracing::instrument(level = "trace")]
+    pub(crate) fn unpack(&self, archive: &Path, dest: &Path) -> anyhow::Result<()> {
+        let file = File::open(archive)?;
+        match self {
+            Self::TarGz => unpack_tar(flate2::read::GzDecoder::new(file), dest),
+            Self::TarXz => unpack_tar(lzma_rust2::XzReader::new(file, true), dest),
+            Self::Zip => unpack_zip(file, dest),
+        }
+    }
+}
+
+impl fmt::Display for ArchiveFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.ext())
+    }
+}
+
+// Unpacks the given tar archive into the given directory.
+#[doc(hidden)]
+fn unpack_tar(reader: impl Read, dest: &Path) -> anyhow::Result<()> {
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+
+        // skip entry with dangerous name
+        let Ok(name) = entry.path() else {
+            let path_bytes = &entry.path_bytes();
+            let name = String::from_utf8_lossy(path_bytes);
+            warn!(name = %name, "skipping dangerous name");
+            continue;
+        };
+
+        let is_dir = entry.header().entry_type().is_dir();
+        let Some(name) = destination(dest, &name, is_dir) else {
+            continue;
+        };
+        trace!("unpacking {name:?}");
+
+        if is_dir {
+            fs::create_dir_all(name)?;
+        } else {
+            create_parent_dir(&name)?;
+            entry.unpack(name)?;
+        }
+    }
+
+    Ok(())
+}
+
+// Unpacks the given zip archive into the given directory.
+#[doc(hidden)]
+fn unpack_zip(file: File, dest: &Path) -> anyhow::Result<()> {
+    let mut zip = zip::ZipArchive::new(file)?;
+    for i in 0..zip.len() {
+        let mut file = zip.by_index(i)?;
+
+        // skip entry with dangerous name
+        let Some(name) = file.enclosed_name() else {
+            warn!(name = file.name(), "skipping dangerous name");
+            continue;
+        };
+
+        let is_dir = file.is_dir();
+        let Some(name) = destination(dest, &name, is_dir) else {
+            continue;
+        };
+        trace!("unpacking {name:?}");
+
+        if is_dir {
+            fs::create_dir_all(name)?;
+        } else {
+            create_parent_dir(&name)?;
+            let mut outfile = File::create(&name)?;
+            io::copy(&mut file, &mut outfile)?;
+
+            // keep permissions (e.g. of bin/java), if there are any
+            #[cfg(unix)]
+            if let Some(mode) = file.unix_mode() {
+                use std::os::unix::fs::PermissionsExt;
+                fs::set_permissions(&name, fs::Permissions::from_mode(mode))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Returns the destination of the entry with the given name within the given directory (without the first component
+// of the name), if the entry is to be unpacked.
+#[doc(hidden)]
+fn destination(dest: &Path, name: &Path, is_dir: bool) -> Option<PathBuf> {
+    // skip elements escaping the destination directory
+    if name
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_) | Component::CurDir))
+    {
+        warn!(name = %name.display(), "skipping dangerous name");
+        return None;
+    }
+
+    // skip elements without at least two components
+    let mut components = name.components().filter(|component| matches!(component, Component::Normal(_)));
+    if components.clone().count() <= 1 {
+        if !is_dir {
+            warn!(name = %name.display(), "skipping unusual name");
+        }
+        return None;
+    }
+
+    // remove the first component
+    components.next();
+
+    Some(dest.join(components.collect::<PathBuf>()))
+}
+
+// Creates the parent directory of the given file, if it does not exist yet.
+#[doc(hidden)]
+fn create_parent_dir(file: &Path) -> io::Result<()> {
+    match file.parent() {
+        Some(parent) if !parent.exists() => fs::create_dir_all(parent),
+        _ => Ok(()),
+    }
+}
+
+/// Returns an error, if the given directory does not contain an unpacked java package.
+pub(crate) fn check_java_home(java_home: &Path) -> anyhow::Result<()> {
+    let bin = java_home.join("bin");
+    if !bin.join("java").exists() && !bin.join("java.exe").exists() {
+        return Err(anyhow!("failed to verify installation"));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::io::Write;
+    use test_log::test;
+
+    // Writes a tar archive containing a fake JDK (and a dangerous entry) into the given writer.
+    fn write_tar(writer: impl Write) -> impl Write {
+        let mut builder = tar::Builder::new(writer);
+        for (name, content) in [("jdk-21/bin/java", "java"), ("jdk-21/release", "JAVA_VERSION=\"21\"")] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o755);
+            header.set_cksum();
+            builder.append_data(&mut header, name, content.as_bytes()).unwrap();
+        }
+
+        // tar::Builder refuses to write dangerous names itself
+        let mut header = tar::Header::new_gnu();
+        header.as_gnu_mut().unwrap().name[..13].copy_from_slice(b"jdk-21/../../");
+        header.set_size(0);
+        header.set_cksum();
+        builder.append(&header, io::empty()).unwrap();
+
+        builder.into_inner().unwrap()
+    }
+
+    // Checks the fake JDK has been unpacked into the given directory.
+    fn assert_unpacked(dest: &Path) {
+        assert_eq!("java", fs::read_to_string(dest.join("bin").join("java")).unwrap());
+        assert!(dest.join("release").exists());
+        assert!(check_java_home(dest).is_ok());
+        assert_eq!(2, fs::read_dir(dest).unwrap().count());
+    }
+
+    #[test]
+    fn formats() {
+        assert_eq!(ArchiveFormat::Zip, ArchiveFormat::for_os("windows"));
+        assert_eq!(ArchiveFormat::TarGz, ArchiveFormat::for_os("linux"));
+        assert_eq!(
+            Some(ArchiveFormat::TarGz),
+            ArchiveFormat::from_url("https://example.com/jdk-21.tar.gz?raw=true")
+        );
+        assert_eq!(Some(ArchiveFormat::TarXz), ArchiveFormat::from_url("https://example.com/jdk-21.TAR.XZ"));
+        assert_eq!(Some(ArchiveFormat::Zip), ArchiveFormat::from_url("jdk-21.zip"));
+        assert_eq!(None, ArchiveFormat::from_url("https://example.com/download/jdk-21"));
+        assert_eq!("\"tar.xz\"", serde_json::to_string(&ArchiveFormat::TarXz).unwrap());
+    }
+
+    #[test]
+    fn dangerous_names() {
+        let dest = Path::new("dest");
+        assert_eq!(Some(dest.join("bin").join("java")), destination(dest, Path::new("./jdk/bin/java"), false));
+        assert_eq!(None, destination(dest, Path::new("jdk/../../etc/passwd"), false));
+        assert_eq!(None, destination(dest, Path::new("/jdk/bin/java"), false));
+        assert_eq!(None, destination(dest, Path::new("jdk"), true));
+    }
+
+    #[test]
+    fn unpack_tar_gz() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let archive = tempdir.path().join("jdk.tar.gz");
+        let encoder = flate2::write::GzEncoder::new(File::create(&archive).unwrap(), flate2::Compression::default());
+        write_tar(encoder).flush().unwrap();
+
+        let dest = tempdir.path().join("dest");
+        ArchiveFormat::TarGz.unpack(&archive, &dest).unwrap();
+        assert_unpacked(&dest);
+    }
+
+    #[test]
+    fn unpack_tar_xz() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let archive = tempdir.path().join("jdk.tar.xz");
+        let mut tar = Vec::new();
+        write_tar(&mut tar);
+        let mut encoder = lzma_rust2::XzWriter::new(File::create(&archive).unwrap(), lzma_rust2::XzOptions::with_preset(1)).unwrap();
+        encoder.write_all(&tar).unwrap();
+        encoder.finish().unwrap();
+
+        let dest = tempdir.path().join("dest");
+        ArchiveFormat::TarXz.unpack(&archive, &dest).unwrap();
+        assert_unpacked(&dest);
+    }
+
+    #[test]
+    fn unpack_zip() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let archive = tempdir.path().join("jdk.zip");
+        let mut zip = zip::ZipWriter::new(File::create(&archive).unwrap());
+        let options = zip::write::SimpleFileOptions::default().unix_permissions(0o755);
+        for (name, content) in [("jdk-21/bin/java", "java"), ("jdk-21/release", "JAVA_VERSION=\"21\""), ("../evil", "")] {
+            zip.start_file(name, options).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let dest = tempdir.path().join("dest");
+        ArchiveFormat::Zip.unpack(&archive, &dest).unwrap();
+        assert_unpacked(&dest);
+        assert!(!tempdir.path().join("evil").exists());
+    }
+}
@@ -2,17 +2,24 @@
 //!
 //! This module contains the definition for the available command-line parameter.
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use std::time::Duration;
 
 #[derive(Debug, Parser)]
 #[clap(author)]
 pub(crate) struct Args {
+    /// The command to execute (updates all installations, if not given)
+    #[clap(subcommand)]
+    pub(crate) command: Option<Command>,
     /// Sets a custom config file
     #[clap(short, long, value_name = "file")]
     pub(crate) config: Option<String>,
     /// Whether to really execute the command
     #[clap(short = 'n', long, action)]
     pub(crate) dry_run: bool,
+    /// Clear installation directories even if they are protected or not managed by java-updater
+    #[clap(long, action)]
+    pub(crate) force: bool,
     /// Suppress unnecessary information
     #[clap(short = 'q', long, action)]
     pub(crate) quiet: bool,
@@ -22,11 +29,31 @@ pub(crate) struct Args {
     /// Change level of verbosity (apply multiple times to increase level)
     #[clap(short, long, action = clap::ArgAction::Count)]
     pub(crate) verbose: u8,
+    /// Wait (at most the given timeout, e.g. 10m, or forever) for locks held by other runs
+    #[clap(long, value_name = "timeout", value_parser = humantime::parse_duration)]
+    pub(crate) wait_lock: Option<Option<Duration>>,
     /// Print version information
     #[clap(short = 'V', long, action)]
     pub(crate) version: bool,
 }
 
+/// Enumeration of the available commands.
+#[derive(Debug, Subcommand)]
+pub(crate) enum Command {
+    /// Adopts the (unmanaged) installation in the given directory by reading its release file
+    Adopt {
+        /// The directory of the installation
+        #[clap(value_name = "directory")]
+        directory: String,
+    },
+    /// Rolls back the installation in the given directory to the previously installed build
+    Rollback {
+        /// The directory of the installation
+        #[clap(value_name = "directory")]
+        directory: String,
+    },
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -45,6 +72,41 @@ mod tests {
         assert!(args.is_err());
     }
 
+    #[test]
+    fn rollback() {
+        let args = Args::try_parse_from(["program", "--dry-run", "rollback", "tmp/eclipse/21"]).unwrap();
+        assert!(args.dry_run);
+        assert!(matches!(args.command, Some(Command::Rollback { directory }) if directory == "tmp/eclipse/21"));
+    }
+
+    #[test]
+    fn adopt() {
+        let args = Args::try_parse_from(["program", "adopt", "tmp/eclipse/21"]).unwrap();
+        assert!(matches!(args.command, Some(Command::Adopt { directory }) if directory == "tmp/eclipse/21"));
+    }
+
+    #[test]
+    fn force() {
+        let args = Args::try_parse_from(["program", "--force"]).unwrap();
+        assert!(args.force);
+    }
+
+    #[test]
+    fn wait_lock() {
+        let args = Args::try_parse_from(["program"]).unwrap();
+        assert_eq!(None, args.wait_lock);
+        let args = Args::try_parse_from(["program", "--wait-lock"]).unwrap();
+        assert_eq!(Some(None), args.wait_lock);
+        let args = Args::try_parse_from(["program", "--wait-lock", "10m"]).unwrap();
+        assert_eq!(Some(Some(Duration::from_secs(600))), args.wait_lock);
+    }
+
+    #[test]
+    fn rollback_without_directory() {
+        let args = Args::try_parse_from(["program", "rollback"]);
+        assert!(args.is_err());
+    }
+
     #[test]
     fn config_with_file() {
         let args = Args::try_parse_from(["program", "--config", "file"]).unwrap();
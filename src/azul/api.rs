@@ -1,175 +1,149 @@
 use super::*;
+use crate::checksum::ChecksumAlgorithm;
 use anyhow::anyhow;
 use reqwest::Url;
 use semver::Version;
 use serde::Deserialize;
-use std::env;
 use tracing::trace;
 
-/// The request to retrieve the metadata.
-pub(super) struct MetadataRequest {
-    pub(super) arch: String,
-    pub(super) os: String,
-    pub(super) package_type: String,
-    pub(super) version: String,
+// Query the Metadata API for all relevant data.
+pub(super) fn query(request: &MetadataRequest) -> anyhow::Result<MetadataResponse> {
+    let (version, url, uuid) = query_packages(request)?;
+    let checksum = query_packages_uuid(&uuid)?;
+
+    Ok(MetadataResponse {
+        checksum,
+        checksum_algorithm: ChecksumAlgorithm::Sha256,
+        companions: Vec::new(),
+        release_date: None,
+        url,
+        version,
+    })
 }
 
-impl MetadataRequest {
-    // Query the Metadata API for all relevant data.
-    pub(super) fn query(&self) -> anyhow::Result<MetadataResponse> {
-        let (version, url, uuid) = self.query_packages()?;
-        let checksum = Self::query_packages_uuid(&uuid)?;
-
-        Ok(MetadataResponse { checksum, url, version })
-    }
-
-    // Query the Metadata API for the package that fulfills the parameter.
-    fn query_packages(&self) -> anyhow::Result<(Version, String, String)> {
-        let url = self.packages_query_url()?;
-        trace!(url = url.as_str());
-        let client = reqwest::blocking::Client::new();
-        let response = client
-            .get(url) //
-            .header(reqwest::header::ACCEPT, "application/json") //
-            .send()?;
-        let bytes = response.bytes()?;
-        let mut de = serde_json::Deserializer::from_slice(&bytes);
-        let response: serde_json::Value = Deserialize::deserialize(&mut de)?;
-        trace!("packages response = {response:#?}");
-
-        // check structure of response
-
-        let Some(response) = response.as_array() else {
-            return Err(anyhow!("response has not the expected structure"));
-        };
-        let arch = self.arch();
-        let response = response
-            .iter()
-            .find(|r| {
-                let name = r["name"].as_str().unwrap_or_default();
-                name.contains(&format!("{arch}.{ARCHIVE_TYPE}"))
-            })
-            .ok_or_else(|| anyhow!("no package found for architecture {arch}"))?;
-
-        // url
-
-        let Some(url) = response["download_url"].as_str() else {
-            return Err(anyhow!("field 'download_url' not present in response"));
-        };
-
-        // version
-
-        let Some(version) = response["java_version"].as_array() else {
-            return Err(anyhow!("field 'java_version' not present in response"));
-        };
-        let Some(major) = version[0].as_u64() else {
-            return Err(anyhow!("major part not present in 'java_version'"));
-        };
-        let Some(minor) = version[1].as_u64() else {
-            return Err(anyhow!("minor part not present in 'java_version'"));
-        };
-        let Some(patch) = version[2].as_u64() else {
-            return Err(anyhow!("patch part not present in 'java_version'"));
-        };
-        let version = Version::new(major, minor, patch);
-
-        // uuid
-
-        let Some(uuid) = response["package_uuid"].as_str() else {
-            return Err(anyhow!("field 'package_uuid' not present in response"));
-        };
-
-        Ok((version, url.to_string(), uuid.to_string()))
-    }
-
-    // Build the query URL to search for packages.
-    fn packages_query_url(&self) -> anyhow::Result<Url> {
-        let mut url = Url::parse(API_URL)?;
-        url.query_pairs_mut()
-            .append_pair("arch", &self.arch())
-            .append_pair("archive_type", ARCHIVE_TYPE)
-            .append_pair("java_version", &self.version())
-            .append_pair("java_package_type", &self.package_type())
-            .append_pair("os", &self.os()) //
-            .append_pair("javafx_bundled", "true")
-            .append_pair("latest", "true")
-            .append_pair("release_status", "ga");
-
-        Ok(url)
-    }
-
-    // Query the Metadata API for details for the package.
-    fn query_packages_uuid(uuid: &str) -> anyhow::Result<String> {
-        let url = Self::packages_uuid_query_url(uuid)?;
-        let client = reqwest::blocking::Client::new();
-        let response = client
-            .get(url) //
-            .header(reqwest::header::ACCEPT, "application/json") //
-            .send()?;
-        let bytes = response.bytes()?;
-        let mut de = serde_json::Deserializer::from_slice(&bytes);
-        let response: serde_json::Value = Deserialize::deserialize(&mut de)?;
-        // checksum
-        let Some(checksum) = response["sha256_hash"].as_str() else {
-            return Err(anyhow!("field 'sha256_hash' not present in response"));
-        };
+// Query the Metadata API for the package that fulfills the parameter.
+fn query_packages(request: &MetadataRequest) -> anyhow::Result<(Version, String, String)> {
+    let url = packages_query_url(request)?;
+    trace!(url = url.as_str());
+    let client = reqwest::blocking::Client::new();
+    let response = client
+        .get(url) //
+        .header(reqwest::header::ACCEPT, "application/json") //
+        .send()?;
+    let bytes = response.bytes()?;
+    let mut de = serde_json::Deserializer::from_slice(&bytes);
+    let response: serde_json::Value = Deserialize::deserialize(&mut de)?;
+    trace!("packages response = {response:#?}");
+
+    // check structure of response
+
+    let Some(response) = response.as_array() else {
+        return Err(anyhow!("response has not the expected structure"));
+    };
+    let arch = arch(request)?;
+    let (version, response) = response
+        .iter()
+        .filter(|r| {
+            let name = r["name"].as_str().unwrap_or_default();
+            name.contains(&format!("{arch}.{}", request.archive_format()))
+        })
+        .filter_map(|r| version(request, r).ok().map(|version| (version, r)))
+        .filter(|(version, _)| request.matches(version))
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .ok_or_else(|| anyhow!("no package found for architecture {arch}"))?;
+
+    // url
+
+    let Some(url) = response["download_url"].as_str() else {
+        return Err(anyhow!("field 'download_url' not present in response"));
+    };
+
+    // uuid
+
+    let Some(uuid) = response["package_uuid"].as_str() else {
+        return Err(anyhow!("field 'package_uuid' not present in response"));
+    };
+
+    Ok((version, url.to_string(), uuid.to_string()))
+}
 
-        Ok(checksum.to_string())
+// Returns the version of the given package.
+fn version(request: &MetadataRequest, response: &serde_json::Value) -> anyhow::Result<Version> {
+    let Some(version) = response["java_version"].as_array() else {
+        return Err(anyhow!("field 'java_version' not present in response"));
+    };
+    let Some(major) = version[0].as_u64() else {
+        return Err(anyhow!("major part not present in 'java_version'"));
+    };
+    let Some(minor) = version[1].as_u64() else {
+        return Err(anyhow!("minor part not present in 'java_version'"));
+    };
+    let Some(patch) = version[2].as_u64() else {
+        return Err(anyhow!("patch part not present in 'java_version'"));
+    };
+    let mut version = Version::new(major, minor, patch);
+    if request.is_early_access() {
+        if let Some(build) = response["openjdk_build_number"].as_u64() {
+            version.build = semver::BuildMetadata::new(&build.to_string())?;
+        }
+        version = early_access(version);
     }
 
-    // Build the query URL to get the package details.
-    fn packages_uuid_query_url(uuid: &str) -> anyhow::Result<Url> {
-        let url = Url::parse(API_URL)?;
-        let url = url.join(uuid)?;
+    Ok(version)
+}
 
-        Ok(url)
+// Build the query URL to search for packages.
+fn packages_query_url(request: &MetadataRequest) -> anyhow::Result<Url> {
+    let mut url = Url::parse(API_URL)?;
+    url.query_pairs_mut()
+        .append_pair("arch", arch(request)?)
+        .append_pair("archive_type", request.archive_format().ext())
+        .append_pair("java_version", &request.version())
+        .append_pair("java_package_type", &request.package_type())
+        .append_pair("os", &request.os()) //
+        .append_pair("javafx_bundled", &request.is_javafx().to_string())
+        .append_pair("latest", &request.is_latest().to_string())
+        .append_pair("release_status", &request.channel());
+    if let Some(libc) = request.libc() {
+        url.query_pairs_mut().append_pair("lib_c_type", &libc);
     }
-
-    // Returns the requested architecture for the package, normalized for the API.
-    fn arch(&self) -> String {
-        let arch = self.arch.trim();
-
-        let arch = if arch.is_empty() { env::consts::ARCH } else { arch };
-
-        match arch.to_lowercase().as_str() {
-            "amd64" | "x86_64" => "x64".to_string(),
-            "x86" => "i686".to_string(),
-            arch => arch.to_string(),
-        }
+    if !request.is_latest() {
+        url.query_pairs_mut().append_pair("page_size", "1000");
     }
 
-    // Returns the requested operating system for the package.
-    fn os(&self) -> String {
-        let os = self.os.trim();
-        if os.is_empty() { env::consts::OS.to_lowercase() } else { os.to_lowercase() }
-    }
+    Ok(url)
+}
 
-    // Returns the requested type for the package.
-    fn package_type(&self) -> String {
-        let package_type = self.package_type.trim();
-        if package_type.is_empty() {
-            return "jdk".to_string(); // default to JDK
-        }
+// Query the Metadata API for details for the package.
+fn query_packages_uuid(uuid: &str) -> anyhow::Result<String> {
+    let url = packages_uuid_query_url(uuid)?;
+    let client = reqwest::blocking::Client::new();
+    let response = client
+        .get(url) //
+        .header(reqwest::header::ACCEPT, "application/json") //
+        .send()?;
+    let bytes = response.bytes()?;
+    let mut de = serde_json::Deserializer::from_slice(&bytes);
+    let response: serde_json::Value = Deserialize::deserialize(&mut de)?;
+    // checksum
+    let Some(checksum) = response["sha256_hash"].as_str() else {
+        return Err(anyhow!("field 'sha256_hash' not present in response"));
+    };
+
+    Ok(checksum.to_string())
+}
 
-        let package_type = package_type.to_lowercase();
-        match package_type.as_str() {
-            "jdk" | "jre" => package_type,
-            _ => "jdk".to_string(), // default to JDK
-        }
-    }
+// Build the query URL to get the package details.
+fn packages_uuid_query_url(uuid: &str) -> anyhow::Result<Url> {
+    let url = Url::parse(API_URL)?;
+    let url = url.join(uuid)?;
 
-    // Returns the requested (major) version for the package.
-    fn version(&self) -> String {
-        let version = self.version.trim();
-        if version.is_empty() { "17".to_string() } else { version.to_lowercase() }
-    }
+    Ok(url)
 }
 
-/// The response to the [`MetadataRequest`].
-pub(super) struct MetadataResponse {
-    pub(super) checksum: String,
-    pub(super) url: String,
-    pub(super) version: Version,
+// Returns the requested architecture for the package, named as used by the API.
+fn arch(request: &MetadataRequest) -> anyhow::Result<&'static str> {
+    request.arch_name(Vendor::Azul)
 }
 
 #[cfg(test)]
@@ -178,6 +152,22 @@ mod tests {
     use super::*;
     use test_log::test;
 
+    #[test]
+    fn test_packages_query_url_musl() {
+        let request = MetadataRequest {
+            arch: "x64".to_string(),
+            libc: "musl".to_string(),
+            os: "linux".to_string(),
+            version: "21".to_string(),
+            ..Default::default()
+        };
+        let url = packages_query_url(&request).unwrap();
+        assert_eq!(
+            "https://api.azul.com/metadata/v1/zulu/packages/?arch=x64&archive_type=tar.gz&java_version=21&java_package_type=jdk&os=linux&javafx_bundled=false&latest=true&release_status=ga&lib_c_type=musl",
+            url.as_str()
+        );
+    }
+
     #[cfg(windows)]
     #[test]
     fn test_normalize_x86_64_architecture() {
@@ -186,8 +176,9 @@ mod tests {
             os: "windows".to_string(),
             package_type: "jdk".to_string(),
             version: "17".to_string(),
+            ..Default::default()
         };
-        assert_eq!("x64", request.arch());
+        assert_eq!("x64", arch(&request).unwrap());
     }
 
     #[cfg(windows)]
@@ -198,8 +189,9 @@ mod tests {
             os: "windows".to_string(),
             package_type: "jdk".to_string(),
             version: "17".to_string(),
+            ..Default::default()
         };
-        let _result = request.query().expect("Failed to query");
+        let _result = query(&request).expect("Failed to query");
         // TODO check response
     }
 
@@ -211,8 +203,9 @@ mod tests {
             os: "windows".to_string(),
             package_type: "jdk".to_string(),
             version: "25".to_string(),
+            ..Default::default()
         };
-        let _result = request.query().expect("Failed to query");
+        let _result = query(&request).expect("Failed to query");
         // TODO check response
     }
 
@@ -224,8 +217,9 @@ mod tests {
             os: "linux".to_string(), // Using linux as a common target for aarch64
             package_type: "jdk".to_string(),
             version: "17".to_string(), // TODO 25 will return two entries
+            ..Default::default()
         };
-        let _result = request.query().expect("Failed to query");
+        let _result = query(&request).expect("Failed to query");
         // TODO check response
     }
 }
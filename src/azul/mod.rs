@@ -1,41 +1,30 @@
 //! Azul.
 //!
-//! This module contains the implementation to download and unpack a java package from Azul.
+//! This module contains the implementation to query java packages from Azul.
 
 // https://docs.azul.com/core/install/metadata-api
 // https://api.azul.com/metadata/v1/docs/swagger
 
 #[doc(hidden)]
 mod api;
-#[doc(hidden)]
-mod installation;
 
-use self::installation::*;
-use crate::args::*;
-use crate::config::InstallationConfig;
-use std::env;
-use std::path::{self, Path};
-use std::rc::Rc;
+use crate::provider::*;
+use crate::vendor::*;
 
 // Base URL for the API endpoint.
 #[doc(hidden)]
 const API_URL: &str = "https://api.azul.com/metadata/v1/zulu/packages/";
 
-// Archive type to be used on OSes other than Windows.
-#[cfg(not(windows))]
-#[doc(hidden)]
-const ARCHIVE_TYPE: &str = "tar.gz";
-
-// Archive type to be used on Windows.
-#[cfg(windows)]
-#[doc(hidden)]
-const ARCHIVE_TYPE: &str = "zip";
+/// The [`VendorProvider`] for Azul.
+#[derive(Debug)]
+pub(crate) struct AzulProvider;
 
-/// Prepare and set up the installation.
-pub(crate) fn setup(basedir: &Path, args: &Args, config: Rc<InstallationConfig>) {
-    let mut installation = Installation::from_config(basedir, config);
+impl VendorProvider for AzulProvider {
+    fn vendor(&self) -> Vendor {
+        Vendor::Azul
+    }
 
-    installation //
-        .dry_run(args.dry_run) //
-        .setup();
+    fn query(&self, request: &MetadataRequest) -> anyhow::Result<MetadataResponse> {
+        api::query(request)
+    }
 }
@@ -1,35 +1,54 @@
 //! Checksum.
 //!
-//! This module contains code to create a checksum (SHA256) "on the fly" while writing data.
+//! This module contains code to create a checksum (SHA1 or SHA256) "on the fly" while writing data.
 
 use digest_io::IoWrapper;
+use sha1::Sha1;
 use sha2::{Digest, Sha256};
 use std::fs::File;
 use std::io::{self, Result, Write};
 use std::path::Path;
 
-// Calculates the checksum (SHA256) for the given file.
-pub(crate) fn checksum(path: &Path) -> Result<String> {
+/// Enumeration of supported checksum algorithms.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum ChecksumAlgorithm {
+    /// SHA1
+    Sha1,
+    /// SHA256
+    #[default]
+    Sha256,
+}
+
+impl ChecksumAlgorithm {
+    /// Returns the algorithm that produced the given (hex encoded) checksum, based on its length.
+    pub(crate) fn from_checksum(checksum: &str) -> Self {
+        match checksum.trim().len() {
+            40 => Self::Sha1,
+            _ => Self::Sha256,
+        }
+    }
+}
+
+// Calculates the checksum with the given algorithm for the given file.
+pub(crate) fn checksum(path: &Path, algorithm: ChecksumAlgorithm) -> Result<String> {
     let mut dest_file = File::open(path)?;
-    let mut hasher = IoWrapper(Sha256::new());
+    let mut hasher = Hasher::new(algorithm);
     io::copy(&mut dest_file, &mut hasher)?;
-    let hash = hasher.0.finalize();
-    let checksum = base16ct::lower::encode_string(&hash);
 
-    Ok(checksum)
+    Ok(hasher.finalize())
 }
 
-/// The struct to create the checksum (SHA256) "on the fly".
+/// The struct to create the checksum "on the fly".
 pub(crate) struct ChecksumWrite<W> {
-    hasher: IoWrapper<Sha256>,
+    hasher: Hasher,
     write: W,
 }
 
 impl<W: Write> ChecksumWrite<W> {
-    /// Creates a new `ChecksumWrite` on top of the given [Write].
-    pub(crate) fn new(write: W) -> Self {
+    /// Creates a new `ChecksumWrite` with the given algorithm on top of the given [Write].
+    pub(crate) fn new(write: W, algorithm: ChecksumAlgorithm) -> Self {
         Self {
-            hasher: IoWrapper(Sha256::new()),
+            hasher: Hasher::new(algorithm),
             write,
         }
     }
@@ -37,10 +56,8 @@ impl<W: Write> ChecksumWrite<W> {
     /// Returns the checksum and consume the `ChecksumWrite`.
     pub(crate) fn checksum(mut self) -> Result<String> {
         self.flush()?;
-        let hash = self.hasher.0.finalize();
-        let checksum = base16ct::lower::encode_string(&hash);
 
-        Ok(checksum)
+        Ok(self.hasher.finalize())
     }
 }
 
@@ -59,3 +76,77 @@ impl<W: Write> Write for ChecksumWrite<W> {
         x.and(y)
     }
 }
+
+// The hasher for the supported checksum algorithms.
+#[doc(hidden)]
+enum Hasher {
+    Sha1(IoWrapper<Sha1>),
+    Sha256(IoWrapper<Sha256>),
+}
+
+impl Hasher {
+    // Creates a new `Hasher` for the given algorithm.
+    fn new(algorithm: ChecksumAlgorithm) -> Self {
+        match algorithm {
+            ChecksumAlgorithm::Sha1 => Self::Sha1(IoWrapper(Sha1::new())),
+            ChecksumAlgorithm::Sha256 => Self::Sha256(IoWrapper(Sha256::new())),
+        }
+    }
+
+    // Returns the (lowercase) hex encoded checksum and consume the `Hasher`.
+    fn finalize(self) -> String {
+        match self {
+            Self::Sha1(hasher) => base16ct::lower::encode_string(&hasher.0.finalize()),
+            Self::Sha256(hasher) => base16ct::lower::encode_string(&hasher.0.finalize()),
+        }
+    }
+}
+
+impl Write for Hasher {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        match self {
+            Self::Sha1(hasher) => hasher.write(buf),
+            Self::Sha256(hasher) => hasher.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        match self {
+            Self::Sha1(hasher) => hasher.flush(),
+            Self::Sha256(hasher) => hasher.flush(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn sha1() {
+        let mut write = ChecksumWrite::new(Vec::new(), ChecksumAlgorithm::Sha1);
+        write.write_all(b"abc").unwrap();
+        assert_eq!("a9993e364706816aba3e25717850c26c9cd0d89d", write.checksum().unwrap());
+    }
+
+    #[test]
+    fn from_checksum() {
+        assert_eq!(
+            ChecksumAlgorithm::Sha1,
+            ChecksumAlgorithm::from_checksum("a9993e364706816aba3e25717850c26c9cd0d89d")
+        );
+        assert_eq!(
+            ChecksumAlgorithm::Sha256,
+            ChecksumAlgorithm::from_checksum("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+        );
+    }
+
+    #[test]
+    fn sha256() {
+        let mut write = ChecksumWrite::new(Vec::new(), ChecksumAlgorithm::Sha256);
+        write.write_all(b"abc").unwrap();
+        assert_eq!("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad", write.checksum().unwrap());
+    }
+}
@@ -2,7 +2,11 @@
 //!
 //! This module contains the configuration read from a YAML file.
 
+use crate::arch::Arch;
+use crate::provider::PACKAGE_TYPES;
 use crate::vars::*;
+use crate::vendor::Vendor;
+use anyhow::anyhow;
 use serde::de::{self, Visitor};
 use serde::{Deserialize, Deserializer};
 use std::borrow::Cow;
@@ -10,7 +14,7 @@ use std::env;
 use std::fmt;
 use std::fs::File;
 use std::marker::PhantomData;
-use std::path::Path;
+use std::path::{self, Path, PathBuf};
 use std::rc::Rc;
 
 /// Name of the default configuration file.
@@ -22,6 +26,9 @@ pub(crate) struct Config {
     /// List with installation configurations.
     #[serde(default)]
     pub(crate) installations: Vec<InstallationConfig>,
+    /// List with protected path prefixes (relative to the configuration file), that must never be cleared.
+    #[serde(default, rename = "protected-paths")]
+    pub(crate) protected_paths: Vec<String>,
 }
 
 impl Config {
@@ -36,35 +43,158 @@ impl Config {
         let de = serde_yaml::Deserializer::from_reader(config_file);
         let value = serde_yaml::Value::deserialize(de)?;
         let config: Config = serde_yaml::from_value(value)?;
+        config.validate()?;
 
         Ok(config)
     }
+
+    // Validates the configuration (i.e. rejects unknown architectures and package types as well as companion
+    // directories outside the installation before any installation gets processed).
+    fn validate(&self) -> anyhow::Result<()> {
+        for installation in &self.installations {
+            let directory = &installation.directory;
+            if let Err(err) = Arch::try_from(installation.architecture.as_str()) {
+                return Err(anyhow!("{err} '{}' for installation at {directory}", installation.architecture));
+            }
+            let package_type = installation.package_type.trim().to_lowercase();
+            if !package_type.is_empty() && !PACKAGE_TYPES.contains(&package_type.as_str()) {
+                return Err(anyhow!(
+                    "unsupported package type '{}' for installation at {directory}",
+                    installation.package_type
+                ));
+            }
+            // without a release date, the build would be held back forever
+            if !installation.policy.min_age.trim().is_empty()
+                && let Ok(vendor) = Vendor::try_from(installation.vendor.as_str())
+                && !vendor.provides_release_date()
+            {
+                return Err(anyhow!("min-age is not supported by {vendor} for installation at {directory}"));
+            }
+            for companion in &installation.companions {
+                let companion_dir = companion.directory();
+                let companion_dir = Path::new(&companion_dir);
+                if companion_dir.as_os_str().is_empty() || !companion_dir.components().all(|c| matches!(c, path::Component::Normal(_))) {
+                    return Err(anyhow!(
+                        "companion directory '{}' outside of installation at {directory}",
+                        companion.directory()
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the protected path prefixes, resolved against the given base directory.
+    pub(crate) fn protected_paths(&self, basedir: &Path) -> Vec<PathBuf> {
+        self.protected_paths
+            .iter()
+            .map(|protected| basedir.join(protected.trim()))
+            .map(|protected| path::absolute(&protected).unwrap_or(protected))
+            .collect()
+    }
 }
 
 /// The configuration for an installation.
 #[derive(Debug, Default, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub(crate) struct InstallationConfig {
+    /// The base URL of the API (e.g. for GitHub Enterprise), only used for GitHub releases.
+    #[cfg(feature = "github-release")]
+    #[serde(default, rename = "api-url")]
+    pub(crate) api_url: String,
     /// The architecture of the installation.
     #[serde(default = "installation_architecture_default")]
     pub(crate) architecture: String,
+    /// The name pattern of the asset, only used for GitHub releases.
+    #[cfg(feature = "github-release")]
+    #[serde(default)]
+    pub(crate) asset: String,
+    /// The bundle of the installation (standard, full or lite).
+    #[cfg(feature = "liberica")]
+    #[serde(default)]
+    pub(crate) bundle: String,
+    /// The release channel of the installation (ga or ea).
+    #[serde(default)]
+    pub(crate) channel: String,
+    /// The name pattern of the checksum asset, only used for GitHub releases.
+    #[cfg(feature = "github-release")]
+    #[serde(default, rename = "checksum-asset")]
+    pub(crate) checksum_asset: String,
+    /// The companion artefacts (e.g. debug image) installed alongside the image.
+    #[serde(default)]
+    pub(crate) companions: Vec<CompanionConfig>,
     /// The directory of the installation.
     pub(crate) directory: String,
+    /// The distribution of the installation (temurin, zulu, corretto, etc.), only used by the Disco API.
+    #[cfg(feature = "disco")]
+    #[serde(default)]
+    pub(crate) distribution: String,
     /// Whether the installation is enabled.
     #[serde(default = "installation_enabled_default")]
     pub(crate) enabled: bool,
-    /// The package type of the installation (JDK or JRE).
-    #[serde(rename = "type")]
+    /// The local file of the package, only used for direct installations.
+    #[cfg(feature = "direct")]
+    #[serde(default)]
+    pub(crate) file: String,
+    /// The JVM implementation of the installation (hotspot or openj9), only used by Eclipse and Semeru.
+    #[cfg(any(feature = "eclipse", feature = "semeru"))]
+    #[serde(default, rename = "jvm-impl")]
+    pub(crate) jvm_impl: String,
+    /// The layout of the installation (flat or versioned).
+    #[serde(default)]
+    pub(crate) layout: String,
+    /// The C library of the installation (glibc or musl), only used on Linux (defaults to the one of the host).
+    #[serde(default = "installation_libc_default")]
+    pub(crate) libc: String,
+    /// The location (URL or path) of the manifest listing the packages, only used by the manifest vendor.
+    #[cfg(feature = "manifest")]
+    #[serde(default)]
+    pub(crate) manifest: String,
+    /// The operating system of the installation (defaults to the one of the host).
+    #[serde(default = "installation_os_default")]
+    pub(crate) os: String,
+    /// The update policy of the installation.
+    #[serde(default)]
+    pub(crate) policy: PolicyConfig,
+    /// The package type of the installation (jdk, jre, jdk+fx or jre+fx).
+    #[serde(default, rename = "type")]
     pub(crate) package_type: String,
+    /// The release (full version) of the package, only used for direct installations.
+    #[cfg(feature = "direct")]
+    #[serde(default, deserialize_with = "installation_version_deser")]
+    pub(crate) release: String,
+    /// The number of versions kept (including the current one), only used by the versioned layout.
+    #[serde(default = "installation_retention_default")]
+    pub(crate) retention: usize,
+    /// The repository (owner/repo), only used for GitHub releases.
+    #[cfg(feature = "github-release")]
+    #[serde(default)]
+    pub(crate) repository: String,
+    /// The checksum (SHA256) of the package, only used for direct installations.
+    #[cfg(feature = "direct")]
+    #[serde(default)]
+    pub(crate) sha256: String,
+    /// The command run against the freshly unpacked build before it replaces the installed one.
+    #[serde(default, rename = "smoke-test")]
+    pub(crate) smoke_test: Option<SmokeTestConfig>,
+    /// The URL of the package, only used for direct installations.
+    #[cfg(feature = "direct")]
+    #[serde(default)]
+    pub(crate) url: String,
     /// The vendor of the installation (Azul, Eclipse, etc.)
     pub(crate) vendor: String,
-    /// The major version of the installation (17, 21, etc.)
-    #[serde(deserialize_with = "installation_version_deser")]
+    /// The version (requirement) of the installation (21, 17.0.x, 17.0.9, >=21.0.2, <21.0.5, etc.)
+    #[serde(default, deserialize_with = "installation_version_deser")]
     pub(crate) version: String,
     /// The command(s) executed on failure.
     #[cfg(feature = "notify")]
     #[serde(default, rename = "on-failure")]
     pub(crate) on_failure: Vec<NotifyCommandConfig>,
+    /// The command(s) executed on rollback.
+    #[cfg(feature = "notify")]
+    #[serde(default, rename = "on-rollback")]
+    pub(crate) on_rollback: Vec<NotifyCommandConfig>,
     /// The command(s) executed on success.
     #[cfg(feature = "notify")]
     #[serde(default, rename = "on-success")]
@@ -89,6 +219,27 @@ fn installation_enabled_default() -> bool {
     true
 }
 
+// Returns the default value for [InstallationConfig::libc].
+#[doc(hidden)]
+#[inline]
+fn installation_libc_default() -> String {
+    "${JU_LIBC}".to_string()
+}
+
+// Returns the default value for [InstallationConfig::os].
+#[doc(hidden)]
+#[inline]
+fn installation_os_default() -> String {
+    "${JU_OS}".to_string()
+}
+
+// Returns the default value for [InstallationConfig::retention].
+#[doc(hidden)]
+#[inline]
+fn installation_retention_default() -> usize {
+    2
+}
+
 // Deserializes the field [InstallationConfig::version] from either unsigned integer or string.
 // see https://serde.rs/string-or-struct.html
 #[doc(hidden)]
@@ -126,14 +277,58 @@ where
 impl InstallationConfig {
     /// Returns [`Installation::directory`] where all known variables are expanded.
     pub(crate) fn expand_directory(config: &Rc<Self>) -> String {
+        Self::expand(config, &config.directory)
+    }
+
+    /// Returns [`Installation::manifest`] where all known variables are expanded.
+    #[cfg(feature = "manifest")]
+    pub(crate) fn expand_manifest(config: &Rc<Self>) -> String {
+        Self::expand(config, &config.manifest)
+    }
+
+    /// Returns [`Installation::file`] where all known variables are expanded.
+    #[cfg(feature = "direct")]
+    pub(crate) fn expand_file(config: &Rc<Self>) -> String {
+        Self::expand(config, &config.file)
+    }
+
+    /// Returns [`Installation::url`] where all known variables are expanded.
+    #[cfg(feature = "direct")]
+    pub(crate) fn expand_url(config: &Rc<Self>) -> String {
+        Self::expand(config, &config.url)
+    }
+
+    /// Returns [`Installation::libc`] where all known variables are expanded.
+    pub(crate) fn expand_libc(config: &Rc<Self>) -> String {
+        Self::expand(config, &config.libc)
+    }
+
+    /// Returns [`Installation::os`] where all known variables are expanded.
+    pub(crate) fn expand_os(config: &Rc<Self>) -> String {
+        Self::expand(config, &config.os)
+    }
+
+    /// Returns [`Installation::asset`] where all known variables are expanded.
+    #[cfg(feature = "github-release")]
+    pub(crate) fn expand_asset(config: &Rc<Self>) -> String {
+        Self::expand(config, &config.asset)
+    }
+
+    /// Returns [`Installation::checksum_asset`] where all known variables are expanded.
+    #[cfg(feature = "github-release")]
+    pub(crate) fn expand_checksum_asset(config: &Rc<Self>) -> String {
+        Self::expand(config, &config.checksum_asset)
+    }
+
+    // Returns the given value where all known variables are expanded.
+    fn expand(config: &Rc<Self>, value: &str) -> String {
         // setup variable resolver(s) and expander
         let env_var_resolver = PrefixedVarResolver::new("env.", Rc::new(OsEnvVarResolver));
         let var_resolvers: Vec<Rc<dyn VarResolver>> = vec![config.clone(), Rc::new(env_var_resolver), Rc::new(RustEnvVarResolver), Rc::new(AsIsVarResolver)];
         let var_expander = VarExpander::new(var_resolvers);
 
         // expand all known variables and leave unknown variables as-is
-        let directory = &config.directory;
-        var_expander.expand(directory).unwrap_or(Cow::Borrowed(directory)).to_string()
+        var_expander.expand(value).unwrap_or(Cow::Borrowed(value)).to_string()
     }
 }
 
@@ -141,7 +336,10 @@ impl VarResolver for InstallationConfig {
     fn resolve_var(&self, var_name: &str) -> Result<String, VarError> {
         let value = match var_name {
             "JU_CONFIG_ARCH" => &self.architecture,
+            "JU_CONFIG_CHANNEL" => &self.channel,
             "JU_CONFIG_DIRECTORY" => &self.directory,
+            "JU_CONFIG_LIBC" => &self.libc,
+            "JU_CONFIG_OS" => &self.os,
             "JU_CONFIG_TYPE" => &self.package_type,
             "JU_CONFIG_VENDOR" => &self.vendor,
             "JU_CONFIG_VERSION" => &self.version,
@@ -152,6 +350,63 @@ impl VarResolver for InstallationConfig {
     }
 }
 
+/// The configuration for the update policy of an installation.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct PolicyConfig {
+    /// Whether updates are checked and reported, but never installed.
+    #[serde(default)]
+    pub(crate) freeze: bool,
+    /// The reaction to an installation in use by running processes (skip, wait or proceed).
+    #[serde(default, rename = "in-use")]
+    pub(crate) in_use: String,
+    /// The maximum time (e.g. 10m) to wait for an installation in use.
+    #[serde(default, rename = "in-use-timeout")]
+    pub(crate) in_use_timeout: String,
+    /// The minimum age (e.g. 7d) of a build before it gets installed.
+    #[serde(default, rename = "min-age")]
+    pub(crate) min_age: String,
+    /// Whether only security (CPU) releases get installed.
+    #[serde(default, rename = "security-only")]
+    pub(crate) security_only: bool,
+}
+
+/// The configuration for a companion artefact (e.g. debug image) of an installation.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct CompanionConfig {
+    /// The type of the artefact (e.g. debugimage, staticlibs or sources).
+    #[serde(rename = "type")]
+    pub(crate) kind: String,
+    /// The directory (relative to the installation) the artefact is unpacked into (defaults to its type).
+    pub(crate) directory: Option<String>,
+}
+
+impl CompanionConfig {
+    /// Returns the type of the artefact.
+    pub(crate) fn kind(&self) -> String {
+        self.kind.trim().to_lowercase()
+    }
+
+    /// Returns the directory (relative to the installation) the artefact is unpacked into.
+    pub(crate) fn directory(&self) -> String {
+        self.directory.as_deref().map_or_else(|| self.kind(), |directory| directory.trim().to_string())
+    }
+}
+
+/// The configuration for a smoke-test command.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct SmokeTestConfig {
+    /// The path to the executable.
+    pub(crate) path: String,
+    /// The arguments for the executable.
+    #[serde(default)]
+    pub(crate) args: Vec<String>,
+    /// The optional working directory for the executable.
+    pub(crate) directory: Option<String>,
+}
+
 /// The configuration for a notify command.
 #[cfg(feature = "notify")]
 #[derive(Debug, Default, Deserialize)]
@@ -200,6 +455,236 @@ mod tests {
         assert_eq!("8", config.version);
     }
 
+    #[cfg(feature = "eclipse")]
+    #[test]
+    fn parse_policy() {
+        let config = r"
+          vendor: eclipse
+          directory: tmp/eclipse/21
+          policy:
+            freeze: true
+            min-age: 7d
+            security-only: true
+        ";
+        let config: InstallationConfig = serde_yaml::from_str(config).unwrap();
+        assert!(config.policy.freeze);
+        assert_eq!("7d", config.policy.min_age);
+        assert!(config.policy.security_only);
+    }
+
+    #[test]
+    fn validate_architecture() {
+        let config = r"
+          installations:
+            - vendor: eclipse
+              directory: tmp/eclipse/21
+              architecture: arm64
+        ";
+        let config: Config = serde_yaml::from_str(config).unwrap();
+        assert!(config.validate().is_ok());
+
+        let config = r"
+          installations:
+            - vendor: eclipse
+              directory: tmp/eclipse/21
+              architecture: sparcv9
+        ";
+        let config: Config = serde_yaml::from_str(config).unwrap();
+        let err = config.validate().unwrap_err();
+        assert_eq!("unsupported architecture 'sparcv9' for installation at tmp/eclipse/21", err.to_string());
+    }
+
+    #[cfg(all(feature = "azul", feature = "eclipse"))]
+    #[test]
+    fn validate_min_age() {
+        let config = r"
+          installations:
+            - vendor: eclipse
+              directory: tmp/eclipse/21
+              policy:
+                min-age: 7d
+        ";
+        let config: Config = serde_yaml::from_str(config).unwrap();
+        assert!(config.validate().is_ok());
+
+        let config = r"
+          installations:
+            - vendor: azul
+              directory: tmp/azul/21
+              policy:
+                min-age: 7d
+        ";
+        let config: Config = serde_yaml::from_str(config).unwrap();
+        let err = config.validate().unwrap_err();
+        assert_eq!("min-age is not supported by Azul for installation at tmp/azul/21", err.to_string());
+    }
+
+    #[test]
+    fn validate_package_type() {
+        let config = r"
+          installations:
+            - vendor: azul
+              directory: tmp/azul/21
+              type: JRE+FX
+        ";
+        let config: Config = serde_yaml::from_str(config).unwrap();
+        assert!(config.validate().is_ok());
+
+        let config = r"
+          installations:
+            - vendor: azul
+              directory: tmp/azul/21
+              type: jdk+javafx
+        ";
+        let config: Config = serde_yaml::from_str(config).unwrap();
+        let err = config.validate().unwrap_err();
+        assert_eq!("unsupported package type 'jdk+javafx' for installation at tmp/azul/21", err.to_string());
+    }
+
+    #[test]
+    fn parse_companions() {
+        let config = r"
+          installations:
+            - vendor: eclipse
+              directory: tmp/eclipse/21
+              companions:
+                - type: DebugImage
+                - type: sources
+                  directory: lib/src
+        ";
+        let config: Config = serde_yaml::from_str(config).unwrap();
+        assert!(config.validate().is_ok());
+        let companions = &config.installations[0].companions;
+        assert_eq!("debugimage", companions[0].kind());
+        assert_eq!("debugimage", companions[0].directory());
+        assert_eq!("sources", companions[1].kind());
+        assert_eq!("lib/src", companions[1].directory());
+
+        let config = r"
+          installations:
+            - vendor: eclipse
+              directory: tmp/eclipse/21
+              companions:
+                - type: sources
+                  directory: ../src
+        ";
+        let config: Config = serde_yaml::from_str(config).unwrap();
+        let err = config.validate().unwrap_err();
+        assert_eq!("companion directory '../src' outside of installation at tmp/eclipse/21", err.to_string());
+    }
+
+    #[test]
+    fn protected_paths() {
+        let config = r"
+          protected-paths:
+            - /opt
+            - data
+        ";
+        let config: Config = serde_yaml::from_str(config).unwrap();
+        let basedir = env::temp_dir();
+        assert_eq!(vec![Path::new("/opt").to_path_buf(), basedir.join("data")], config.protected_paths(&basedir));
+    }
+
+    #[test]
+    fn parse_layout() {
+        let config = r"
+          vendor: eclipse
+          directory: tmp/eclipse/21
+          layout: versioned
+          retention: 3
+        ";
+        let config: InstallationConfig = serde_yaml::from_str(config).unwrap();
+        assert_eq!("versioned", config.layout);
+        assert_eq!(3, config.retention);
+
+        let config = r"
+          vendor: eclipse
+          directory: tmp/eclipse/21
+        ";
+        let config: InstallationConfig = serde_yaml::from_str(config).unwrap();
+        assert_eq!("", config.layout);
+        assert_eq!(2, config.retention);
+    }
+
+    #[test]
+    fn parse_os() {
+        let config = r"
+          vendor: eclipse
+          directory: tmp/eclipse/21/${JU_CONFIG_OS}
+          os: windows
+        ";
+        let config = Rc::new(serde_yaml::from_str::<InstallationConfig>(config).unwrap());
+        assert_eq!("windows", InstallationConfig::expand_os(&config));
+        assert_eq!("tmp/eclipse/21/windows", InstallationConfig::expand_directory(&config));
+
+        let config = r"
+          vendor: eclipse
+          directory: tmp/eclipse/21/${JU_CONFIG_OS}
+        ";
+        let config = Rc::new(serde_yaml::from_str::<InstallationConfig>(config).unwrap());
+        assert_eq!(env::consts::OS, InstallationConfig::expand_os(&config));
+        assert_eq!(format!("tmp/eclipse/21/{}", env::consts::OS), InstallationConfig::expand_directory(&config));
+    }
+
+    #[test]
+    fn parse_libc() {
+        let config = r"
+          vendor: azul
+          directory: tmp/azul/21/${JU_CONFIG_LIBC}
+          libc: musl
+        ";
+        let config = Rc::new(serde_yaml::from_str::<InstallationConfig>(config).unwrap());
+        assert_eq!("musl", InstallationConfig::expand_libc(&config));
+        assert_eq!("tmp/azul/21/musl", InstallationConfig::expand_directory(&config));
+
+        let config = r"
+          vendor: azul
+          directory: tmp/azul/21
+        ";
+        let config = Rc::new(serde_yaml::from_str::<InstallationConfig>(config).unwrap());
+        assert_eq!(host_libc(), InstallationConfig::expand_libc(&config));
+    }
+
+    #[cfg(feature = "direct")]
+    #[test]
+    fn parse_direct() {
+        let config = r"
+          vendor: direct
+          directory: tmp/direct/17
+          url: https://example.com/jdk-17.0.9-patched.tar.gz
+          sha256: abcd
+          release: 17.0.9
+        ";
+        let config: InstallationConfig = serde_yaml::from_str(config).unwrap();
+        assert_eq!("17.0.9", config.release);
+        assert_eq!("abcd", config.sha256);
+        assert_eq!("https://example.com/jdk-17.0.9-patched.tar.gz", config.url);
+        assert_eq!("", config.version);
+    }
+
+    #[cfg(feature = "github-release")]
+    #[test]
+    fn parse_github_release() {
+        let config = r#"
+          vendor: github-release
+          directory: tmp/jbr/21
+          repository: JetBrains/JetBrainsRuntime
+          asset: "jbr_jcef-${JU_RELEASE_VERSION}-linux-${JU_CONFIG_ARCH}-*.tar.gz"
+          checksum-asset: "jbr_jcef-${JU_RELEASE_VERSION}-linux-${JU_CONFIG_ARCH}-*.tar.gz.checksum"
+          api-url: https://github.example.com/api/v3/
+          version: 21
+        "#;
+        let config: InstallationConfig = serde_yaml::from_str(config).unwrap();
+        assert_eq!("https://github.example.com/api/v3/", config.api_url);
+        assert_eq!("JetBrains/JetBrainsRuntime", config.repository);
+        let config = Rc::new(config);
+        let architecture = &config.architecture;
+        assert_eq!(
+            format!("jbr_jcef-${{JU_RELEASE_VERSION}}-linux-{architecture}-*.tar.gz"),
+            InstallationConfig::expand_asset(&config)
+        );
+    }
+
     #[test]
     fn expand_directory() {
         let architecture = env::consts::ARCH.to_string();
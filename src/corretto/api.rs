@@ -0,0 +1,164 @@
+use super::*;
+use crate::checksum::ChecksumAlgorithm;
+use anyhow::anyhow;
+use reqwest::Url;
+use semver::{BuildMetadata, Version};
+use tracing::trace;
+
+// Query the permanent download links for all relevant data.
+pub(super) fn query(request: &MetadataRequest) -> anyhow::Result<MetadataResponse> {
+    if request.is_early_access() {
+        return Err(anyhow!("early-access builds not available"));
+    }
+
+    let filename = filename(request)?;
+
+    // resolve the permanent link to the versioned download link
+    let url = Url::parse(DOWNLOAD_URL)?.join(&filename)?;
+    trace!(url = url.as_str());
+    let client = reqwest::blocking::Client::new();
+    let response = client
+        .head(url) //
+        .send()?
+        .error_for_status()?;
+    let url = response.url().clone();
+    trace!(resolved_url = url.as_str());
+
+    // version
+
+    let version = version_from_url(&url)?;
+    // only the latest version is available via the permanent download links
+    if !request.matches(&version) {
+        return Err(anyhow!("latest version {version} does not fulfill version requirement {}", request.version));
+    }
+
+    // checksum
+
+    let checksum_url = Url::parse(CHECKSUM_URL)?.join(&filename)?;
+    trace!(checksum_url = checksum_url.as_str());
+    let response = client
+        .get(checksum_url) //
+        .header(reqwest::header::ACCEPT, "text/plain") //
+        .send()?
+        .error_for_status()?;
+    let checksum = response.text()?;
+    let Some(checksum) = checksum.split_whitespace().next() else {
+        return Err(anyhow!("checksum not present in response"));
+    };
+
+    Ok(MetadataResponse {
+        checksum: checksum.to_lowercase(),
+        checksum_algorithm: ChecksumAlgorithm::Sha256,
+        companions: Vec::new(),
+        release_date: None,
+        url: url.to_string(),
+        version,
+    })
+}
+
+// Returns the filename of the permanent download link for the package.
+fn filename(request: &MetadataRequest) -> anyhow::Result<String> {
+    let version = request.version();
+    let arch = arch(request)?;
+    let os = os(request);
+    let package_type = request.package_type();
+    let archive_format = request.archive_format();
+    Ok(format!("amazon-corretto-{version}-{arch}-{os}-{package_type}.{archive_format}"))
+}
+
+// Extracts the version out of the resolved download link (.../resources/<version>/<filename>).
+fn version_from_url(url: &Url) -> anyhow::Result<Version> {
+    let version = url
+        .path_segments()
+        .and_then(|mut segments| segments.nth_back(1))
+        .ok_or_else(|| anyhow!("version not present in '{url}'"))?;
+
+    parse_version(version)
+}
+
+// Parses a Corretto version (e.g. 17.0.9.8.1 or 8.392.08.1) into a semantic version.
+fn parse_version(version: &str) -> anyhow::Result<Version> {
+    let parts: Vec<&str> = version.split('.').collect();
+    let number = |i: usize| -> anyhow::Result<u64> {
+        let part = parts.get(i).ok_or_else(|| anyhow!("part {i} not present in '{version}'"))?;
+        part.parse().map_err(|_| anyhow!("part {i} of '{version}' is not a number"))
+    };
+
+    // Corretto 8 uses <major>.<update>.<build>.<revision>
+    let major = number(0)?;
+    let (minor, patch, build) = if major == 8 {
+        (0, number(1)?, &parts[2..])
+    } else {
+        (number(1)?, number(2)?, &parts[3..])
+    };
+
+    let mut version = Version::new(major, minor, patch);
+    if !build.is_empty() {
+        version.build = BuildMetadata::new(&build.join("."))?;
+    }
+
+    Ok(version)
+}
+
+// Returns the requested architecture for the package, named as used by the permanent download links.
+fn arch(request: &MetadataRequest) -> anyhow::Result<&'static str> {
+    request.arch_name(Vendor::Corretto)
+}
+
+// Returns the requested operating system for the package, normalized for the permanent download links.
+fn os(request: &MetadataRequest) -> String {
+    match request.os().as_str() {
+        "darwin" | "mac" | "macosx" => "macos".to_string(),
+        os => os.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn test_normalize_x86_64_architecture() {
+        let request = MetadataRequest {
+            arch: "x86_64".to_string(),
+            os: "linux".to_string(),
+            package_type: "jdk".to_string(),
+            version: "17".to_string(),
+            ..Default::default()
+        };
+        assert_eq!("x64", arch(&request).unwrap());
+    }
+
+    #[test]
+    fn test_filename() {
+        let request = MetadataRequest {
+            arch: "aarch64".to_string(),
+            os: "linux".to_string(),
+            package_type: "jdk".to_string(),
+            version: "21".to_string(),
+            ..Default::default()
+        };
+        assert_eq!("amazon-corretto-21-aarch64-linux-jdk.tar.gz", filename(&request).unwrap());
+    }
+
+    #[test]
+    fn test_version_from_url() {
+        let url = Url::parse("https://corretto.aws/downloads/resources/17.0.9.8.1/amazon-corretto-17.0.9.8.1-linux-x64.tar.gz").unwrap();
+        let version = version_from_url(&url).unwrap();
+        assert_eq!(Version::parse("17.0.9+8.1").unwrap(), version);
+    }
+
+    #[test]
+    fn test_parse_version_8() {
+        let version = parse_version("8.392.08.1").unwrap();
+        assert_eq!(Version::parse("8.0.392+08.1").unwrap(), version);
+    }
+
+    #[test]
+    fn test_parse_version_invalid() {
+        assert!(parse_version("17").is_err());
+        assert!(parse_version("17.x.1").is_err());
+    }
+}
@@ -0,0 +1,33 @@
+//! Corretto.
+//!
+//! This module contains the implementation to query java packages from Amazon (Corretto).
+
+// https://docs.aws.amazon.com/corretto/latest/corretto-21-ug/downloads-list.html
+
+#[doc(hidden)]
+mod api;
+
+use crate::provider::*;
+use crate::vendor::*;
+
+// Base URL for the permanent download links.
+#[doc(hidden)]
+const DOWNLOAD_URL: &str = "https://corretto.aws/downloads/latest/";
+
+// Base URL for the checksums of the permanent download links.
+#[doc(hidden)]
+const CHECKSUM_URL: &str = "https://corretto.aws/downloads/latest_sha256/";
+
+/// The [`VendorProvider`] for Amazon (Corretto).
+#[derive(Debug)]
+pub(crate) struct CorrettoProvider;
+
+impl VendorProvider for CorrettoProvider {
+    fn vendor(&self) -> Vendor {
+        Vendor::Corretto
+    }
+
+    fn query(&self, request: &MetadataRequest) -> anyhow::Result<MetadataResponse> {
+        api::query(request)
+    }
+}
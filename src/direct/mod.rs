@@ -0,0 +1,126 @@
+//! Direct.
+//!
+//! This module contains the implementation for java packages that are directly configured by URL or local file.
+//!
+//! As the configuration already contains everything (URL/file, checksum and release), no API is queried at all.
+//! Subsequent runs are no-ops until the configuration changes.
+
+use crate::checksum::ChecksumAlgorithm;
+use crate::provider::*;
+use crate::vendor::*;
+use anyhow::anyhow;
+use reqwest::Url;
+
+/// The [`VendorProvider`] for direct installations.
+#[derive(Debug)]
+pub(crate) struct DirectProvider;
+
+impl VendorProvider for DirectProvider {
+    fn vendor(&self) -> Vendor {
+        Vendor::Direct
+    }
+
+    fn query(&self, request: &MetadataRequest) -> anyhow::Result<MetadataResponse> {
+        // url
+
+        let file = request.file.trim();
+        let url = request.url.trim();
+        let url = match (file.is_empty(), url.is_empty()) {
+            (true, true) => return Err(anyhow!("neither 'url' nor 'file' configured")),
+            (false, false) => return Err(anyhow!("only one of 'url' or 'file' may be configured")),
+            (false, true) => Url::from_file_path(file).map_err(|()| anyhow!("invalid file {file}"))?.to_string(),
+            (true, false) => Url::parse(url)?.to_string(),
+        };
+
+        // checksum
+
+        let checksum = request.sha256.trim().to_lowercase();
+        if checksum.is_empty() {
+            return Err(anyhow!("no 'sha256' configured"));
+        }
+
+        // version
+
+        let release = request.release.trim();
+        if release.is_empty() {
+            return Err(anyhow!("no 'release' configured"));
+        }
+        let version = parse_version(release)?;
+
+        Ok(MetadataResponse {
+            checksum,
+            checksum_algorithm: ChecksumAlgorithm::Sha256,
+            companions: Vec::new(),
+            release_date: None,
+            url,
+            version,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use semver::Version;
+    use test_log::test;
+
+    #[test]
+    fn query_url() {
+        let request = MetadataRequest {
+            release: "17.0.9+9".to_string(),
+            sha256: "ABCD".to_string(),
+            url: "https://example.com/jdk-17.0.9-patched.tar.gz".to_string(),
+            ..Default::default()
+        };
+        let response = DirectProvider.query(&request).unwrap();
+        assert_eq!("abcd", response.checksum);
+        assert_eq!("https://example.com/jdk-17.0.9-patched.tar.gz", response.url);
+        assert_eq!(Version::parse("17.0.9+9").unwrap(), response.version);
+    }
+
+    #[test]
+    fn query_file() {
+        let file = std::env::temp_dir().join("jdk.tar.gz");
+        let request = MetadataRequest {
+            file: file.to_string_lossy().to_string(),
+            release: "21.0.1".to_string(),
+            sha256: "abcd".to_string(),
+            ..Default::default()
+        };
+        let response = DirectProvider.query(&request).unwrap();
+        assert_eq!(Url::from_file_path(file).unwrap().as_str(), response.url);
+    }
+
+    #[test]
+    fn query_url_and_file() {
+        let request = MetadataRequest {
+            file: "jdk.tar.gz".to_string(),
+            release: "21.0.1".to_string(),
+            sha256: "abcd".to_string(),
+            url: "https://example.com/jdk.tar.gz".to_string(),
+            ..Default::default()
+        };
+        assert!(DirectProvider.query(&request).is_err());
+    }
+
+    #[test]
+    fn query_without_checksum() {
+        let request = MetadataRequest {
+            release: "21.0.1".to_string(),
+            url: "https://example.com/jdk.tar.gz".to_string(),
+            ..Default::default()
+        };
+        assert!(DirectProvider.query(&request).is_err());
+    }
+
+    #[test]
+    fn query_without_release() {
+        let request = MetadataRequest {
+            sha256: "abcd".to_string(),
+            url: "https://example.com/jdk.tar.gz".to_string(),
+            ..Default::default()
+        };
+        assert!(DirectProvider.query(&request).is_err());
+    }
+}
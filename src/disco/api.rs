@@ -0,0 +1,211 @@
+use super::*;
+use crate::checksum::ChecksumAlgorithm;
+use anyhow::anyhow;
+use reqwest::Url;
+use reqwest::blocking::Client;
+use serde::Deserialize;
+use tracing::trace;
+
+// Query the API for all relevant data.
+pub(super) fn query(request: &MetadataRequest) -> anyhow::Result<MetadataResponse> {
+    let client = Client::new();
+    let (version, id) = query_packages(&client, request)?;
+    let (url, checksum, checksum_algorithm) = query_ids(&client, &id)?;
+
+    Ok(MetadataResponse {
+        checksum,
+        checksum_algorithm,
+        companions: Vec::new(),
+        release_date: None,
+        url,
+        version,
+    })
+}
+
+// Query the API for the newest package that fulfills the parameter.
+fn query_packages(client: &Client, request: &MetadataRequest) -> anyhow::Result<(semver::Version, String)> {
+    let url = packages_query_url(request)?;
+    trace!(url = url.as_str());
+    let response = get_json(client, url)?;
+    trace!("packages response = {response:#?}");
+
+    // check structure of response
+
+    let Some(response) = response["result"].as_array() else {
+        return Err(anyhow!("response has not the expected structure"));
+    };
+    // the C library is only a filter (not a requirement) for the API, so check it again
+    let libc = request.libc();
+    let response = response
+        .iter()
+        .filter(|r| r["archive_type"].as_str() == Some(request.archive_format().ext()))
+        .filter(|r| libc.is_none() || r["lib_c_type"].as_str() == libc.as_deref())
+        .filter_map(|r| {
+            let version = r["java_version"].as_str()?;
+            let version = parse_version(version).ok()?;
+            Some((version, r))
+        })
+        .filter(|(version, _)| request.matches(version))
+        .max_by(|(a, _), (b, _)| a.cmp(b));
+    let distribution = distribution(request)?;
+    let Some((version, response)) = response else {
+        return Err(anyhow!("no package found for distribution {distribution}"));
+    };
+
+    // id
+
+    let Some(id) = response["id"].as_str() else {
+        return Err(anyhow!("field 'id' not present in response"));
+    };
+
+    Ok((version, id.to_string()))
+}
+
+// Build the query URL to search for packages.
+fn packages_query_url(request: &MetadataRequest) -> anyhow::Result<Url> {
+    let mut url = Url::parse(API_URL)?.join("packages")?;
+    url.query_pairs_mut()
+        .append_pair("architecture", arch(request)?)
+        .append_pair("archive_type", request.archive_format().ext())
+        .append_pair("directly_downloadable", "true")
+        .append_pair("distribution", &distribution(request)?)
+        .append_pair("javafx_bundled", &request.is_javafx().to_string());
+    if request.is_latest() {
+        url.query_pairs_mut().append_pair("latest", "available");
+    }
+    if let Some(libc) = request.libc() {
+        url.query_pairs_mut().append_pair("lib_c_type", &libc);
+    }
+    url.query_pairs_mut()
+        .append_pair("operating_system", &request.os())
+        .append_pair("package_type", &request.package_type())
+        .append_pair("release_status", &request.channel())
+        .append_pair("version", &request.version());
+
+    Ok(url)
+}
+
+// Query the API for the download URL and checksum of the package with the given id.
+fn query_ids(client: &Client, id: &str) -> anyhow::Result<(String, String, ChecksumAlgorithm)> {
+    let url = Url::parse(API_URL)?.join("ids/")?.join(id)?;
+    trace!(url = url.as_str());
+    let response = get_json(client, url)?;
+    trace!("ids response = {response:#?}");
+
+    // check structure of response
+
+    let Some(response) = response["result"].as_array().and_then(|r| r.first()) else {
+        return Err(anyhow!("response has not the expected structure"));
+    };
+
+    // url
+
+    let Some(url) = response["direct_download_uri"].as_str() else {
+        return Err(anyhow!("field 'direct_download_uri' not present in response"));
+    };
+
+    // checksum (either directly or via a separate file published as "<checksum>  <filename>")
+
+    let checksum_algorithm = match response["checksum_type"].as_str().unwrap_or_default().to_lowercase().as_str() {
+        "sha1" => ChecksumAlgorithm::Sha1,
+        "sha256" | "" => ChecksumAlgorithm::Sha256,
+        checksum_type => return Err(anyhow!("unsupported checksum type {checksum_type}")),
+    };
+    let checksum = match response["checksum"].as_str() {
+        Some(checksum) if !checksum.trim().is_empty() => checksum.trim().to_string(),
+        _ => {
+            let Some(checksum_url) = response["checksum_uri"].as_str().filter(|uri| !uri.is_empty()) else {
+                return Err(anyhow!("neither field 'checksum' nor 'checksum_uri' present in response"));
+            };
+            trace!(checksum_url);
+            let response = client.get(checksum_url).send()?.error_for_status()?;
+            let checksum = response.text()?;
+            let Some(checksum) = checksum.split_whitespace().next() else {
+                return Err(anyhow!("checksum not present in response"));
+            };
+            checksum.to_string()
+        }
+    };
+
+    Ok((url.to_string(), checksum.to_lowercase(), checksum_algorithm))
+}
+
+// Performs a GET request for the given URL and returns the JSON response.
+fn get_json(client: &Client, url: Url) -> anyhow::Result<serde_json::Value> {
+    let response = client
+        .get(url) //
+        .header(reqwest::header::ACCEPT, "application/json") //
+        .send()?;
+    let bytes = response.bytes()?;
+    let mut de = serde_json::Deserializer::from_slice(&bytes);
+    let response: serde_json::Value = Deserialize::deserialize(&mut de)?;
+
+    Ok(response)
+}
+
+// Returns the requested architecture for the package, named as used by the API.
+fn arch(request: &MetadataRequest) -> anyhow::Result<&'static str> {
+    request.arch_name(Vendor::Disco)
+}
+
+// Returns the requested distribution for the package.
+fn distribution(request: &MetadataRequest) -> anyhow::Result<String> {
+    let distribution = request.distribution.trim().to_lowercase();
+    if distribution.is_empty() {
+        return Err(anyhow!("no distribution configured"));
+    }
+
+    Ok(distribution)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn test_packages_query_url() {
+        let request = MetadataRequest {
+            arch: "x86_64".to_string(),
+            distribution: "Temurin".to_string(),
+            os: "linux".to_string(),
+            package_type: "jre".to_string(),
+            version: "21".to_string(),
+            ..Default::default()
+        };
+        let url = packages_query_url(&request).unwrap();
+        let query = url.query().unwrap_or_default();
+        assert!(query.starts_with("architecture=x64&"));
+        assert!(query.contains("&distribution=temurin&"));
+        assert!(query.contains("&operating_system=linux&package_type=jre&release_status=ga&version=21"));
+    }
+
+    #[test]
+    fn test_packages_query_url_musl() {
+        let request = MetadataRequest {
+            arch: "x86_64".to_string(),
+            distribution: "zulu".to_string(),
+            libc: "musl".to_string(),
+            os: "linux".to_string(),
+            version: "21".to_string(),
+            ..Default::default()
+        };
+        let url = packages_query_url(&request).unwrap();
+        let query = url.query().unwrap_or_default();
+        assert!(query.contains("&lib_c_type=musl&operating_system=linux&"));
+
+        let request = MetadataRequest {
+            os: "windows".to_string(),
+            ..request
+        };
+        let url = packages_query_url(&request).unwrap();
+        assert!(!url.query().unwrap_or_default().contains("lib_c_type"));
+    }
+
+    #[test]
+    fn test_missing_distribution() {
+        let request = MetadataRequest::default();
+        assert!(packages_query_url(&request).is_err());
+    }
+}
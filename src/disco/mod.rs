@@ -0,0 +1,30 @@
+//! Disco.
+//!
+//! This module contains the implementation to query java packages of various distributions from the foojay Discovery API.
+
+// https://github.com/foojayio/discoapi
+// https://api.foojay.io/swagger-ui
+
+#[doc(hidden)]
+mod api;
+
+use crate::provider::*;
+use crate::vendor::*;
+
+// Base URL for the API endpoint.
+#[doc(hidden)]
+const API_URL: &str = "https://api.foojay.io/disco/v3.0/";
+
+/// The [`VendorProvider`] for the foojay Discovery API.
+#[derive(Debug)]
+pub(crate) struct DiscoProvider;
+
+impl VendorProvider for DiscoProvider {
+    fn vendor(&self) -> Vendor {
+        Vendor::Disco
+    }
+
+    fn query(&self, request: &MetadataRequest) -> anyhow::Result<MetadataResponse> {
+        api::query(request)
+    }
+}
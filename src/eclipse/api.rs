@@ -1,144 +1,234 @@
 use super::*;
+use crate::checksum::ChecksumAlgorithm;
 use anyhow::anyhow;
 use reqwest::Url;
 use semver::Version;
 use serde::Deserialize;
-use std::env;
 use tracing::trace;
 
-/// The request to retrieve the metadata.
-pub(super) struct MetadataRequest {
-    pub(super) arch: String,
-    pub(super) os: String,
-    pub(super) package_type: String,
-    pub(super) version: String,
+// Query the API for all relevant data (including the requested companion artefacts).
+pub(super) fn query(request: &MetadataRequest) -> anyhow::Result<MetadataResponse> {
+    let mut response = query_image(request, &request.package_type())?;
+    for kind in &request.companions {
+        let companion = query_image(request, kind)?;
+        if companion.version != response.version {
+            return Err(anyhow!("companion {kind} not available for version {}", response.version));
+        }
+        response.companions.push(MetadataCompanion {
+            checksum: companion.checksum,
+            kind: kind.clone(),
+            url: companion.url,
+        });
+    }
+
+    Ok(response)
+}
+
+// Query the API for the image of the given type (e.g. jdk or debugimage).
+fn query_image(request: &MetadataRequest, image_type: &str) -> anyhow::Result<MetadataResponse> {
+    if request.is_early_access() || !request.is_latest() {
+        return query_feature_releases(request, image_type);
+    }
+
+    let url = query_url(request, image_type)?;
+    trace!(?url);
+    let client = reqwest::blocking::Client::new();
+    let response = client
+        .get(url) //
+        .header(reqwest::header::ACCEPT, "application/json") //
+        .send()?;
+    let bytes = response.bytes()?;
+    let mut de = serde_json::Deserializer::from_slice(&bytes);
+    let response: serde_json::Value = Deserialize::deserialize(&mut de)?;
+    trace!("response = {response:#?}");
+
+    // check structure of response
+
+    let Some(response) = response.as_array() else {
+        return Err(anyhow!("response has not the expected structure"));
+    };
+    let arch = arch(request)?;
+    let response = response
+        .iter()
+        .find(|r| {
+            let r_arch = r["binary"]["architecture"].as_str().unwrap_or_default();
+            r_arch == arch // Direct comparison after normalization
+        })
+        .ok_or_else(|| anyhow!("no package found for architecture {arch}"))?;
+
+    // url
+
+    let Some(url) = response["binary"]["package"]["link"].as_str() else {
+        return Err(anyhow!("field 'link' not present in response"));
+    };
+
+    // checksum
+
+    let Some(checksum) = response["binary"]["package"]["checksum"].as_str() else {
+        return Err(anyhow!("field 'checksum' not present in response"));
+    };
+
+    // version
+
+    if !response["version"].is_object() {
+        return Err(anyhow!("field 'version' not present in response"));
+    }
+    let version = release_version(&response["version"], false)?;
+
+    Ok(MetadataResponse {
+        checksum: checksum.to_string(),
+        checksum_algorithm: ChecksumAlgorithm::Sha256,
+        companions: Vec::new(),
+        release_date: response["binary"]["updated_at"].as_str().and_then(parse_release_date),
+        url: url.to_string(),
+        version,
+    })
 }
 
-impl MetadataRequest {
-    // Query the API for all relevant data.
-    pub(super) fn query(&self) -> anyhow::Result<MetadataResponse> {
-        let url = self.query_url()?;
+// Query the API for the newest (early-access) image of the given type that fulfills the version requirement.
+//
+// The releases are sorted by version (newest first), so the pages are fetched until a page contains a matching release
+// or there are no more pages.
+fn query_feature_releases(request: &MetadataRequest, image_type: &str) -> anyhow::Result<MetadataResponse> {
+    let client = reqwest::blocking::Client::new();
+    let mut page = 0;
+    let (version, release) = loop {
+        let url = feature_releases_query_url(request, image_type, page)?;
         trace!(?url);
-        let client = reqwest::blocking::Client::new();
         let response = client
             .get(url) //
             .header(reqwest::header::ACCEPT, "application/json") //
             .send()?;
-        let bytes = response.bytes()?;
+        // pages beyond the last one are reported as not found
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(anyhow!("no release found for version {}", request.version));
+        }
+        let bytes = response.error_for_status()?.bytes()?;
         let mut de = serde_json::Deserializer::from_slice(&bytes);
         let response: serde_json::Value = Deserialize::deserialize(&mut de)?;
         trace!("response = {response:#?}");
 
         // check structure of response
 
-        let Some(response) = response.as_array() else {
+        let Some(releases) = response.as_array() else {
             return Err(anyhow!("response has not the expected structure"));
         };
-        let arch = self.arch();
-        let response = response
+        let release = releases
             .iter()
-            .find(|r| {
-                let r_arch = r["binary"]["architecture"].as_str().unwrap_or_default();
-                r_arch == arch // Direct comparison after normalization
-            })
-            .ok_or_else(|| anyhow!("no package found for architecture {arch}"))?;
+            .filter_map(|r| release_version(&r["version_data"], request.is_early_access()).ok().map(|version| (version, r)))
+            .filter(|(version, _)| request.matches(version))
+            .max_by(|(a, _), (b, _)| a.cmp(b));
+        if let Some((version, release)) = release {
+            break (version, release.clone());
+        }
+        if releases.len() < PAGE_SIZE {
+            return Err(anyhow!("no release found for version {}", request.version));
+        }
+        page += 1;
+    };
+    let Some(binaries) = release["binaries"].as_array() else {
+        return Err(anyhow!("field 'binaries' not present in response"));
+    };
+    let arch = arch(request)?;
+    let binary = binaries
+        .iter()
+        .find(|b| b["architecture"].as_str().unwrap_or_default() == arch)
+        .ok_or_else(|| anyhow!("no package found for architecture {arch}"))?;
 
-        // url
+    // url
 
-        let Some(url) = response["binary"]["package"]["link"].as_str() else {
-            return Err(anyhow!("field 'link' not present in response"));
-        };
+    let Some(url) = binary["package"]["link"].as_str() else {
+        return Err(anyhow!("field 'link' not present in response"));
+    };
 
-        // checksum
+    // checksum
 
-        let Some(checksum) = response["binary"]["package"]["checksum"].as_str() else {
-            return Err(anyhow!("field 'checksum' not present in response"));
-        };
+    let Some(checksum) = binary["package"]["checksum"].as_str() else {
+        return Err(anyhow!("field 'checksum' not present in response"));
+    };
 
-        // version
+    Ok(MetadataResponse {
+        checksum: checksum.to_string(),
+        checksum_algorithm: ChecksumAlgorithm::Sha256,
+        companions: Vec::new(),
+        release_date: binary["updated_at"].as_str().and_then(parse_release_date),
+        url: url.to_string(),
+        version,
+    })
+}
 
-        let Some(version) = response["version"].as_object() else {
-            return Err(anyhow!("field 'version' not present in response"));
-        };
-        let Some(major) = version["major"].as_u64() else {
-            return Err(anyhow!("major part not present in 'version'"));
-        };
-        let Some(minor) = version["minor"].as_u64() else {
-            return Err(anyhow!("minor part not present in 'version'"));
-        };
-        let Some(security) = version["security"].as_u64() else {
-            return Err(anyhow!("security part not present in 'version'"));
+// Returns the version (e.g. 21.0.5, 11.0.20+1 for out-of-band releases or 26.0.0-ea.20 for early-access builds) from
+// the given version data.
+fn release_version(version: &serde_json::Value, prerelease: bool) -> anyhow::Result<Version> {
+    let Some(major) = version["major"].as_u64() else {
+        return Err(anyhow!("major part not present in 'version_data'"));
+    };
+    let minor = version["minor"].as_u64().unwrap_or_default();
+    let security = version["security"].as_u64().unwrap_or_default();
+    if !prerelease {
+        // the patch part (e.g. 11.0.20.1) is kept as build metadata, like the other vendors do
+        return match version["patch"].as_u64() {
+            Some(patch) if patch > 0 => parse_version(&format!("{major}.{minor}.{security}.{patch}")),
+            _ => Ok(Version::new(major, minor, security)),
         };
-        let version = Version::new(major, minor, security);
-
-        Ok(MetadataResponse {
-            checksum: checksum.to_string(),
-            url: url.to_string(),
-            version,
-        })
     }
 
-    // Build the query URL to search for packages.
-    fn query_url(&self) -> anyhow::Result<Url> {
-        let mut version = self.version();
-        version.push('/');
-        let url = Url::parse(API_URL)?;
-        let url = url.join(&version)?;
-        let mut url = url.join("hotspot/")?;
-        url.query_pairs_mut()
-            .append_pair("architecture", &self.arch())
-            .append_pair("image_type", &self.package_type())
-            .append_pair("os", &self.os())
-            .append_pair("vendor", "eclipse");
-
-        Ok(url)
-    }
+    let version = match version["build"].as_u64() {
+        Some(build) => Version::parse(&format!("{major}.{minor}.{security}+{build}"))?,
+        None => Version::new(major, minor, security),
+    };
 
-    // Returns the requested architecture for the package, normalized for the API.
-    fn arch(&self) -> String {
-        let arch = self.arch.trim();
+    Ok(early_access(version))
+}
 
-        let arch = if arch.is_empty() { env::consts::ARCH } else { arch };
+// Build the query URL to search for (early-access) releases of the requested feature version with an image of the given
+// type (the given page of the results).
+fn feature_releases_query_url(request: &MetadataRequest, image_type: &str, page: usize) -> anyhow::Result<Url> {
+    let mut version = request.version();
+    version.push('/');
+    let url = Url::parse(FEATURE_RELEASES_API_URL)?;
+    let url = url.join(&version)?;
+    let mut url = url.join(&request.channel())?;
+    url.query_pairs_mut()
+        .append_pair("architecture", arch(request)?)
+        .append_pair("image_type", image_type)
+        .append_pair("jvm_impl", &request.jvm_impl())
+        .append_pair("os", &os(request))
+        .append_pair("page", &page.to_string())
+        .append_pair("page_size", &PAGE_SIZE.to_string())
+        .append_pair("sort_order", "DESC")
+        .append_pair("vendor", "eclipse");
 
-        match arch.to_lowercase().as_str() {
-            "amd64" | "x86_64" => "x64".to_string(),
-            "i686" | "x86" => "x32".to_string(),
-            arch => arch.to_string(),
-        }
-    }
+    Ok(url)
+}
 
-    // Returns the requested operating system for the package.
-    fn os(&self) -> String {
-        let os = self.os.trim();
-        if os.is_empty() { env::consts::OS.to_lowercase() } else { os.to_lowercase() }
-    }
+// Build the query URL to search for packages with an image of the given type.
+fn query_url(request: &MetadataRequest, image_type: &str) -> anyhow::Result<Url> {
+    let mut version = request.version();
+    version.push('/');
+    let url = Url::parse(API_URL)?;
+    let url = url.join(&version)?;
+    let mut url = url.join(&format!("{}/", request.jvm_impl()))?;
+    url.query_pairs_mut()
+        .append_pair("architecture", arch(request)?)
+        .append_pair("image_type", image_type)
+        .append_pair("os", &os(request))
+        .append_pair("vendor", "eclipse");
 
-    // Returns the requested type for the package.
-    fn package_type(&self) -> String {
-        let package_type = self.package_type.trim();
-        if package_type.is_empty() {
-            return "jdk".to_string(); // default to JDK
-        }
-
-        let package_type = package_type.to_lowercase();
-        match package_type.as_str() {
-            "jdk" | "jre" => package_type,
-            _ => "jdk".to_string(), // default to JDK
-        }
-    }
+    Ok(url)
+}
 
-    // Returns the requested (major) version for the package.
-    fn version(&self) -> String {
-        let version = self.version.trim();
-        if version.is_empty() { "17".to_string() } else { version.to_lowercase() }
+// Returns the requested operating system for the package, normalized for the API (musl builds are published for alpine-linux).
+fn os(request: &MetadataRequest) -> String {
+    match request.libc().as_deref() {
+        Some("musl") => "alpine-linux".to_string(),
+        _ => request.os(),
     }
 }
 
-/// The response to the [`MetadataRequest`].
-pub(super) struct MetadataResponse {
-    pub(super) checksum: String,
-    pub(super) url: String,
-    pub(super) version: Version,
+// Returns the requested architecture for the package, named as used by the API.
+fn arch(request: &MetadataRequest) -> anyhow::Result<&'static str> {
+    request.arch_name(Vendor::Eclipse)
 }
 
 #[cfg(test)]
@@ -154,8 +244,9 @@ mod tests {
             os: "windows".to_string(),
             package_type: "jdk".to_string(),
             version: "17".to_string(),
+            ..Default::default()
         };
-        assert_eq!("x32", request.arch());
+        assert_eq!("x32", arch(&request).unwrap());
     }
 
     #[test]
@@ -165,8 +256,85 @@ mod tests {
             os: "windows".to_string(),
             package_type: "jdk".to_string(),
             version: "17".to_string(),
+            ..Default::default()
+        };
+        assert_eq!("x64", arch(&request).unwrap());
+    }
+
+    #[test]
+    fn test_feature_releases_query_url() {
+        let request = MetadataRequest {
+            arch: "x86_64".to_string(),
+            channel: "ea".to_string(),
+            libc: "glibc".to_string(),
+            os: "linux".to_string(),
+            version: "26".to_string(),
+            ..Default::default()
+        };
+        let url = feature_releases_query_url(&request, "jdk", 1).unwrap();
+        assert_eq!(
+            "https://api.adoptium.net/v3/assets/feature_releases/26/ea?architecture=x64&image_type=jdk&jvm_impl=hotspot&os=linux&page=1&page_size=20&sort_order=DESC&vendor=eclipse",
+            url.as_str()
+        );
+    }
+
+    #[test]
+    fn test_query_url_openj9() {
+        let request = MetadataRequest {
+            arch: "x64".to_string(),
+            jvm_impl: "OpenJ9".to_string(),
+            libc: "glibc".to_string(),
+            os: "linux".to_string(),
+            version: "21".to_string(),
+            ..Default::default()
         };
-        assert_eq!("x64", request.arch());
+        let url = query_url(&request, "jdk").unwrap();
+        assert_eq!(
+            "https://api.adoptium.net/v3/assets/latest/21/openj9/?architecture=x64&image_type=jdk&os=linux&vendor=eclipse",
+            url.as_str()
+        );
+    }
+
+    #[test]
+    fn test_query_url_musl() {
+        let request = MetadataRequest {
+            arch: "x64".to_string(),
+            libc: "musl".to_string(),
+            os: "linux".to_string(),
+            version: "21".to_string(),
+            ..Default::default()
+        };
+        let url = query_url(&request, "jdk").unwrap();
+        assert_eq!(
+            "https://api.adoptium.net/v3/assets/latest/21/hotspot/?architecture=x64&image_type=jdk&os=alpine-linux&vendor=eclipse",
+            url.as_str()
+        );
+    }
+
+    #[test]
+    fn test_query_url_debugimage() {
+        let request = MetadataRequest {
+            arch: "aarch64".to_string(),
+            libc: "glibc".to_string(),
+            os: "linux".to_string(),
+            version: "21".to_string(),
+            ..Default::default()
+        };
+        let url = query_url(&request, "debugimage").unwrap();
+        assert_eq!(
+            "https://api.adoptium.net/v3/assets/latest/21/hotspot/?architecture=aarch64&image_type=debugimage&os=linux&vendor=eclipse",
+            url.as_str()
+        );
+    }
+
+    #[test]
+    fn test_release_version() {
+        let version_data = serde_json::json!({"build": 20, "major": 26, "minor": 0, "security": 0});
+        assert_eq!(Version::parse("26.0.0-ea.20").unwrap(), release_version(&version_data, true).unwrap());
+        assert_eq!(Version::new(26, 0, 0), release_version(&version_data, false).unwrap());
+        let version_data = serde_json::json!({"build": 1, "major": 11, "minor": 0, "patch": 1, "security": 20});
+        assert_eq!(Version::parse("11.0.20+1").unwrap(), release_version(&version_data, false).unwrap());
+        assert!(release_version(&version_data, false).unwrap() > Version::new(11, 0, 20));
     }
 
     #[test]
@@ -176,8 +344,9 @@ mod tests {
             os: "windows".to_string(),
             package_type: "jdk".to_string(),
             version: "17".to_string(),
+            ..Default::default()
         };
-        let _result = request.query().expect("Failed to query");
+        let _result = query(&request).expect("Failed to query");
         // TODO check response
     }
 
@@ -188,8 +357,9 @@ mod tests {
             os: "windows".to_string(),
             package_type: "jdk".to_string(),
             version: "25".to_string(),
+            ..Default::default()
         };
-        let _result = request.query().expect("Failed to query");
+        let _result = query(&request).expect("Failed to query");
         // TODO check response
     }
 
@@ -200,8 +370,9 @@ mod tests {
             os: "linux".to_string(),
             package_type: "jdk".to_string(),
             version: "17".to_string(),
+            ..Default::default()
         };
-        let _result = request.query().expect("Failed to query");
+        let _result = query(&request).expect("Failed to query");
         // TODO check response
     }
 }
@@ -1,40 +1,37 @@
 //! Eclipse.
 //!
-//! This module contains the implementation to download and unpack a java package from Eclipse.
+//! This module contains the implementation to query java packages from Eclipse.
 
 // https://api.adoptium.net/q/swagger-ui/
 
 #[doc(hidden)]
 mod api;
-#[doc(hidden)]
-mod installation;
 
-use self::installation::*;
-use crate::args::*;
-use crate::config::InstallationConfig;
-use std::env;
-use std::path::{self, Path};
-use std::rc::Rc;
+use crate::provider::*;
+use crate::vendor::*;
 
 // Base URL for the API endpoint.
 #[doc(hidden)]
 const API_URL: &str = "https://api.adoptium.net/v3/assets/latest/";
 
-// Archive type to be used on OSes other than Windows.
-#[cfg(not(windows))]
+// Base URL for the API endpoint listing (early-access) releases.
 #[doc(hidden)]
-const ARCHIVE_TYPE: &str = "tar.gz";
+const FEATURE_RELEASES_API_URL: &str = "https://api.adoptium.net/v3/assets/feature_releases/";
 
-// Archive type to be used on Windows.
-#[cfg(windows)]
+// Number of releases per page requested from the API endpoint listing (early-access) releases.
 #[doc(hidden)]
-const ARCHIVE_TYPE: &str = "zip";
+const PAGE_SIZE: usize = 20;
+
+/// The [`VendorProvider`] for Eclipse.
+#[derive(Debug)]
+pub(crate) struct EclipseProvider;
 
-/// Prepare and set up the installation.
-pub(crate) fn setup(basedir: &Path, args: &Args, config: Rc<InstallationConfig>) {
-    let mut installation = Installation::from_config(basedir, config);
+impl VendorProvider for EclipseProvider {
+    fn vendor(&self) -> Vendor {
+        Vendor::Eclipse
+    }
 
-    installation //
-        .dry_run(args.dry_run) //
-        .setup();
+    fn query(&self, request: &MetadataRequest) -> anyhow::Result<MetadataResponse> {
+        api::query(request)
+    }
 }
@@ -0,0 +1,274 @@
+//! GitHub.
+//!
+//! This module contains the code shared by all vendors that are based on GitHub releases.
+
+// https://docs.github.com/en/rest/releases/releases
+
+#[cfg(any(feature = "sapmachine", feature = "semeru"))]
+use crate::provider::{MetadataRequest, early_access};
+use anyhow::anyhow;
+use reqwest::Url;
+use reqwest::blocking::{Client, RequestBuilder};
+#[cfg(any(feature = "sapmachine", feature = "semeru"))]
+use semver::{Op, Version, VersionReq};
+use serde::Deserialize;
+use std::env;
+use tracing::trace;
+
+/// Base URL for the API endpoint (public GitHub).
+pub(crate) const API_URL: &str = "https://api.github.com/";
+
+// Environment variable holding an (optional) token to authenticate against the API.
+#[doc(hidden)]
+const ENV_GITHUB_TOKEN: &str = "GITHUB_TOKEN";
+
+// Number of releases per page.
+#[doc(hidden)]
+const PER_PAGE: u32 = 100;
+
+/// Maximum number of pages to search through (for releases of arbitrary repositories).
+#[cfg(feature = "github-release")]
+pub(crate) const MAX_PAGES: u32 = 5;
+
+/// Query one page of releases of the given repository (owner/repo).
+pub(crate) fn releases(client: &Client, api_url: &str, repository: &str, page: u32) -> anyhow::Result<Vec<serde_json::Value>> {
+    let mut api_url = api_url.trim().to_string();
+    if !api_url.ends_with('/') {
+        api_url.push('/');
+    }
+    let repository = repository.trim().trim_matches('/');
+    let mut url = Url::parse(&api_url)?.join(&format!("repos/{repository}/releases"))?;
+    url.query_pairs_mut() //
+        .append_pair("page", &page.to_string())
+        .append_pair("per_page", &PER_PAGE.to_string());
+    trace!(url = url.as_str());
+    let response = get(client, url.as_str()) //
+        .header(reqwest::header::ACCEPT, "application/vnd.github+json") //
+        .send()?
+        .error_for_status()?;
+    let bytes = response.bytes()?;
+    let mut de = serde_json::Deserializer::from_slice(&bytes);
+    let response: serde_json::Value = Deserialize::deserialize(&mut de)?;
+
+    // check structure of response
+
+    let Some(releases) = response.as_array() else {
+        return Err(anyhow!("response has not the expected structure"));
+    };
+
+    Ok(releases.clone())
+}
+
+/// Query the release with the highest version fulfilling the given request out of the releases of the given
+/// repository (owner/repo) and return it together with its version.
+///
+/// The version of a release is taken from its tag by the given function (releases without version are skipped).
+/// Releases are listed newest first, so only if the requirement fixes the major version (e.g. 21, 17.0.x or 17.0.9)
+/// the search stops after the first page with a match, as newer releases of other major versions can't match.
+#[cfg(any(feature = "sapmachine", feature = "semeru"))]
+pub(crate) fn latest_release<F>(
+    client: &Client,
+    repository: &str,
+    request: &MetadataRequest,
+    version_from_tag: F,
+) -> anyhow::Result<(serde_json::Value, Version)>
+where
+    F: Fn(&str) -> Option<Version>,
+{
+    let major_fixed = request.version_req().is_ok_and(|req| is_major_fixed(&req));
+    let mut latest: Option<(serde_json::Value, Version)> = None;
+    for page in 1.. {
+        let releases = releases(client, API_URL, repository, page)?;
+        let last_page = releases.len() < PER_PAGE as usize;
+        if let Some((release, version)) = newest(&releases, request, &version_from_tag)
+            && latest.as_ref().is_none_or(|(_, latest)| version > *latest)
+        {
+            latest = Some((release.clone(), version));
+        }
+        if last_page || (major_fixed && latest.is_some()) {
+            break;
+        }
+    }
+
+    latest.ok_or_else(|| anyhow!("no release found for version {}", request.version))
+}
+
+// Returns the release with the highest version fulfilling the given request out of the given releases.
+#[cfg(any(feature = "sapmachine", feature = "semeru"))]
+#[doc(hidden)]
+fn newest<'a, F>(releases: &'a [serde_json::Value], request: &MetadataRequest, version_from_tag: F) -> Option<(&'a serde_json::Value, Version)>
+where
+    F: Fn(&str) -> Option<Version>,
+{
+    releases
+        .iter()
+        .filter_map(|release| {
+            // skip drafts and (unless early-access builds are requested) pre-releases
+            let prerelease = is_prerelease(release);
+            if is_draft(release) || (prerelease && !request.is_early_access()) {
+                return None;
+            }
+
+            // skip releases without version or not fulfilling the version requirement
+            let version = release["tag_name"].as_str().and_then(&version_from_tag)?;
+            if !request.matches(&version) {
+                return None;
+            }
+            let version = if prerelease { early_access(version) } else { version };
+
+            Some((release, version))
+        })
+        .max_by(|(_, a), (_, b)| a.cmp(b))
+}
+
+// Returns whether the given requirement fixes the major version (e.g. 21, 17.0.x or 17.0.9).
+#[cfg(any(feature = "sapmachine", feature = "semeru"))]
+#[doc(hidden)]
+fn is_major_fixed(req: &VersionReq) -> bool {
+    req.comparators
+        .iter()
+        .any(|comparator| matches!(comparator.op, Op::Exact | Op::Tilde | Op::Caret | Op::Wildcard))
+}
+
+/// Returns whether the given release is a draft.
+pub(crate) fn is_draft(release: &serde_json::Value) -> bool {
+    release["draft"].as_bool().unwrap_or_default()
+}
+
+/// Returns whether the given release is a pre-release.
+pub(crate) fn is_prerelease(release: &serde_json::Value) -> bool {
+    release["prerelease"].as_bool().unwrap_or_default()
+}
+
+/// Returns the first asset of the given release whose name matches the given predicate.
+pub(crate) fn find_asset<P>(release: &serde_json::Value, predicate: P) -> Option<&serde_json::Value>
+where
+    P: Fn(&str) -> bool,
+{
+    release["assets"]
+        .as_array()?
+        .iter()
+        .find(|asset| asset["name"].as_str().is_some_and(&predicate))
+}
+
+/// Returns the checksum (SHA256) for the given asset.
+///
+/// The checksum is taken from the given checksum asset (published as "<checksum>  <filename>") or,
+/// if there is none, from the digest GitHub calculated for the asset.
+pub(crate) fn checksum(client: &Client, asset: &serde_json::Value, checksum_asset: Option<&serde_json::Value>) -> anyhow::Result<String> {
+    let name = asset["name"].as_str().unwrap_or_default();
+
+    if let Some(checksum_asset) = checksum_asset {
+        let Some(url) = checksum_asset["browser_download_url"].as_str() else {
+            return Err(anyhow!("field 'browser_download_url' not present in response"));
+        };
+        trace!(checksum_url = url);
+        let response = get(client, url).send()?.error_for_status()?;
+        let checksums = response.text()?;
+        return parse_checksum(&checksums, name).ok_or_else(|| anyhow!("checksum for {name} not present in {url}"));
+    }
+
+    match asset["digest"].as_str().and_then(|digest| digest.strip_prefix("sha256:")) {
+        Some(checksum) => Ok(checksum.to_lowercase()),
+        None => Err(anyhow!("no checksum available for {name}")),
+    }
+}
+
+/// Returns a GET request for the given URL (authenticated, if a token is available).
+pub(crate) fn get(client: &Client, url: &str) -> RequestBuilder {
+    let request = client.get(url).header(reqwest::header::USER_AGENT, env!("CARGO_PKG_NAME"));
+    match env::var(ENV_GITHUB_TOKEN) {
+        Ok(token) if !token.trim().is_empty() => request.bearer_auth(token.trim()),
+        _ => request,
+    }
+}
+
+// Parses the checksum for the given filename out of the given checksums (one "<checksum>  <filename>" per line).
+#[doc(hidden)]
+fn parse_checksum(checksums: &str, filename: &str) -> Option<String> {
+    let mut lines = checksums.lines().filter(|line| !line.trim().is_empty());
+    let first = lines.clone().next()?;
+
+    // prefer the line that mentions the filename, fall back to the first line otherwise
+    let line = lines
+        .find(|line| {
+            let name = line.split_whitespace().nth(1).unwrap_or_default();
+            name.trim_start_matches('*') == filename
+        })
+        .unwrap_or(first);
+
+    line.split_whitespace().next().map(str::to_lowercase)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn parse_checksum_single() {
+        assert_eq!(Some("abcd".to_string()), parse_checksum("ABCD  jdk.tar.gz\n", "jdk.tar.gz"));
+        assert_eq!(Some("abcd".to_string()), parse_checksum("abcd", "jdk.tar.gz"));
+    }
+
+    #[test]
+    fn parse_checksum_multiple() {
+        let checksums = "1111  jdk.zip\n2222 *jdk.tar.gz\n3333  jre.tar.gz\n";
+        assert_eq!(Some("2222".to_string()), parse_checksum(checksums, "jdk.tar.gz"));
+    }
+
+    #[test]
+    fn parse_checksum_empty() {
+        assert_eq!(None, parse_checksum("\n", "jdk.tar.gz"));
+    }
+
+    #[test]
+    fn find_asset_by_name() {
+        let release = serde_json::json!({ "assets": [{ "name": "a.zip" }, { "name": "b.tar.gz" }] });
+        let asset = find_asset(&release, |name| name.ends_with(".tar.gz")).unwrap();
+        assert_eq!(Some("b.tar.gz"), asset["name"].as_str());
+    }
+
+    #[cfg(any(feature = "sapmachine", feature = "semeru"))]
+    #[test]
+    fn newest_by_version() {
+        let releases = serde_json::json!([
+            { "tag_name": "17.0.13", "draft": false, "prerelease": false },
+            { "tag_name": "21.0.4", "draft": false, "prerelease": false },
+            { "tag_name": "21.0.6", "draft": true, "prerelease": false },
+            { "tag_name": "21.0.5", "draft": false, "prerelease": false },
+            { "tag_name": "21.0.6-ea+1", "draft": false, "prerelease": true },
+        ]);
+        let releases = releases.as_array().unwrap();
+        let request = |version: &str| MetadataRequest {
+            version: version.to_string(),
+            ..Default::default()
+        };
+        let version_from_tag = |tag: &str| crate::provider::parse_version(tag).ok();
+
+        let (release, version) = newest(releases, &request("21"), version_from_tag).unwrap();
+        assert_eq!(Some("21.0.5"), release["tag_name"].as_str());
+        assert_eq!(Version::new(21, 0, 5), version);
+        let (_, version) = newest(releases, &request("17"), version_from_tag).unwrap();
+        assert_eq!(Version::new(17, 0, 13), version);
+        assert!(newest(releases, &request("11"), version_from_tag).is_none());
+    }
+
+    #[cfg(any(feature = "sapmachine", feature = "semeru"))]
+    #[test]
+    fn major_fixed() {
+        let req = |req: &str| VersionReq::parse(req).unwrap();
+        assert!(is_major_fixed(&req("^21")));
+        assert!(is_major_fixed(&req("=17.0.9")));
+        assert!(is_major_fixed(&req("17.0.*")));
+        assert!(!is_major_fixed(&req(">=21.0.2, <22")));
+    }
+
+    #[test]
+    fn checksum_from_digest() {
+        let client = Client::new();
+        let asset = serde_json::json!({ "name": "jdk.tar.gz", "digest": "sha256:ABCD" });
+        assert_eq!("abcd", checksum(&client, &asset, None).unwrap());
+    }
+}
@@ -0,0 +1,200 @@
+use super::*;
+use crate::checksum::ChecksumAlgorithm;
+use crate::github;
+use crate::vars::*;
+use anyhow::anyhow;
+use reqwest::blocking::Client;
+use semver::Version;
+use std::borrow::Cow;
+use std::rc::Rc;
+use tracing::trace;
+
+// Variable holding the tag of the release.
+#[doc(hidden)]
+const VAR_JU_RELEASE_TAG: &str = "JU_RELEASE_TAG";
+
+// Variable holding the version extracted from the tag of the release.
+#[doc(hidden)]
+const VAR_JU_RELEASE_VERSION: &str = "JU_RELEASE_VERSION";
+
+// Query the API for all relevant data.
+pub(super) fn query(request: &MetadataRequest) -> anyhow::Result<MetadataResponse> {
+    let repository = request.repository.trim();
+    if repository.is_empty() {
+        return Err(anyhow!("no 'repository' configured"));
+    }
+    let asset_pattern = request.asset.trim();
+    if asset_pattern.is_empty() {
+        return Err(anyhow!("no 'asset' configured"));
+    }
+    let api_url = request.api_url.trim();
+    let api_url = if api_url.is_empty() { github::API_URL } else { api_url };
+
+    // search for the newest release with a matching asset
+    let client = Client::new();
+    let mut newest: Option<(Version, serde_json::Value, VarExpander)> = None;
+    for page in 1..=github::MAX_PAGES {
+        let releases = github::releases(&client, api_url, repository, page)?;
+        if releases.is_empty() {
+            break;
+        }
+
+        for release in releases {
+            // skip drafts and (unless early-access builds are requested) pre-releases
+            let prerelease = github::is_prerelease(&release);
+            if github::is_draft(&release) || (prerelease && !request.is_early_access()) {
+                continue;
+            }
+
+            // skip releases without version or not fulfilling the version requirement (if any)
+            let Some(tag) = release["tag_name"].as_str() else {
+                continue;
+            };
+            let Some((version_str, version)) = version_from_tag(tag) else {
+                trace!(tag, "skipping release without version");
+                continue;
+            };
+            if !request.version.trim().is_empty() && !request.matches(&version) {
+                continue;
+            }
+            let version = if prerelease { early_access(version) } else { version };
+
+            // skip releases without matching asset
+            let var_expander = var_expander(tag, version_str);
+            let pattern = var_expander.expand(asset_pattern).unwrap_or(Cow::Borrowed(asset_pattern));
+            if github::find_asset(&release, |name| matches(&pattern, name)).is_none() {
+                continue;
+            }
+
+            if newest.as_ref().is_none_or(|(newest, _, _)| version > *newest) {
+                newest = Some((version, release, var_expander));
+            }
+        }
+
+        // releases are sorted by date, so do not search any further
+        if newest.is_some() {
+            break;
+        }
+    }
+    let Some((version, release, var_expander)) = newest else {
+        return Err(anyhow!("no release with asset {asset_pattern} found in {repository}"));
+    };
+
+    // url
+
+    let pattern = var_expander.expand(asset_pattern).unwrap_or(Cow::Borrowed(asset_pattern));
+    let Some(asset) = github::find_asset(&release, |name| matches(&pattern, name)) else {
+        return Err(anyhow!("no asset {pattern} found"));
+    };
+    let Some(url) = asset["browser_download_url"].as_str() else {
+        return Err(anyhow!("field 'browser_download_url' not present in response"));
+    };
+
+    // checksum
+
+    let checksum_pattern = request.checksum_asset.trim();
+    let checksum_asset = if checksum_pattern.is_empty() {
+        None
+    } else {
+        let pattern = var_expander.expand(checksum_pattern).unwrap_or(Cow::Borrowed(checksum_pattern));
+        let Some(checksum_asset) = github::find_asset(&release, |name| matches(&pattern, name)) else {
+            return Err(anyhow!("no checksum asset {pattern} found"));
+        };
+        Some(checksum_asset)
+    };
+    let checksum = github::checksum(&client, asset, checksum_asset)?;
+
+    Ok(MetadataResponse {
+        checksum,
+        checksum_algorithm: ChecksumAlgorithm::Sha256,
+        companions: Vec::new(),
+        release_date: release["published_at"].as_str().and_then(parse_release_date),
+        url: url.to_string(),
+        version,
+    })
+}
+
+// Returns the variable expander for the release specific variables.
+fn var_expander(tag: &str, version: &str) -> VarExpander {
+    let mut simple_var_resolver = SimpleVarResolver::new();
+    simple_var_resolver.insert(VAR_JU_RELEASE_TAG, tag);
+    simple_var_resolver.insert(VAR_JU_RELEASE_VERSION, version);
+    let var_resolvers: Vec<Rc<dyn VarResolver>> = vec![Rc::new(simple_var_resolver), Rc::new(AsIsVarResolver)];
+
+    VarExpander::new(var_resolvers)
+}
+
+// Extracts the version (first sequence of digits and dots, e.g. 21.0.5 out of jbr-release-21.0.5b631.8) out of the given tag.
+fn version_from_tag(tag: &str) -> Option<(&str, Version)> {
+    let start = tag.find(|c: char| c.is_ascii_digit())?;
+    let tag = &tag[start..];
+    let end = tag.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(tag.len());
+    let version_str = tag[..end].trim_end_matches('.');
+    let version = parse_version(version_str).ok()?;
+
+    Some((version_str, version))
+}
+
+// Returns whether the given name matches the given pattern (supporting `*` and `?` as wildcards).
+fn matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = backtrack {
+            p = star_p + 1;
+            n = star_n + 1;
+            backtrack = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn test_matches() {
+        assert!(matches("jbr_jcef-21.0.5-linux-x64-*.tar.gz", "jbr_jcef-21.0.5-linux-x64-b631.8.tar.gz"));
+        assert!(matches("*.tar.gz", "x.tar.gz"));
+        assert!(matches("jdk-??.zip", "jdk-21.zip"));
+        assert!(!matches("jbr_jcef-*-linux-x64-*.tar.gz", "jbr_jcef-21.0.5-linux-x64-b631.8.tar.gz.checksum"));
+        assert!(!matches("jdk-?.zip", "jdk-21.zip"));
+    }
+
+    #[test]
+    fn test_version_from_tag() {
+        assert_eq!(Some(("21.0.5", Version::new(21, 0, 5))), version_from_tag("jbr-release-21.0.5b631.8"));
+        assert_eq!(Some(("21.0.5", Version::new(21, 0, 5))), version_from_tag("TencentKona-21.0.5"));
+        assert_eq!(None, version_from_tag("latest"));
+    }
+
+    #[test]
+    fn test_var_expander() {
+        let var_expander = var_expander("jbr-release-21.0.5b631.8", "21.0.5");
+        let expanded = var_expander.expand("jbr-${JU_RELEASE_VERSION}-${JU_OS}.tar.gz").unwrap();
+        assert_eq!("jbr-21.0.5-${JU_OS}.tar.gz", expanded);
+    }
+
+    #[test]
+    fn test_query_without_repository() {
+        let request = MetadataRequest {
+            asset: "*.tar.gz".to_string(),
+            ..Default::default()
+        };
+        assert!(query(&request).is_err());
+    }
+}
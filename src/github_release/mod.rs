@@ -0,0 +1,30 @@
+//! GitHub Releases.
+//!
+//! This module contains the implementation to query java packages published as GitHub release assets
+//! (e.g. JetBrains Runtime, Alibaba Dragonwell or Tencent Kona).
+//!
+//! The assets are selected by name patterns (supporting `*` and `?` as wildcards), which may contain
+//! the usual variables as well as the following release specific variables:
+//!
+//! - `JU_RELEASE_TAG` (the tag of the release, e.g. `jbr-release-21.0.5b631.8`)
+//! - `JU_RELEASE_VERSION` (the version extracted from the tag, e.g. `21.0.5`)
+
+#[doc(hidden)]
+mod api;
+
+use crate::provider::*;
+use crate::vendor::*;
+
+/// The [`VendorProvider`] for GitHub releases.
+#[derive(Debug)]
+pub(crate) struct GithubReleaseProvider;
+
+impl VendorProvider for GithubReleaseProvider {
+    fn vendor(&self) -> Vendor {
+        Vendor::GithubRelease
+    }
+
+    fn query(&self, request: &MetadataRequest) -> anyhow::Result<MetadataResponse> {
+        api::query(request)
+    }
+}
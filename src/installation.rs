@@ -0,0 +1,934 @@
+//! Installation.
+//!
+//! This module contains the vendor independent pipeline to set up an installation.
+
+use crate::archive::ArchiveFormat;
+use crate::checksum::ChecksumAlgorithm;
+use crate::config::*;
+use crate::inuse;
+use crate::journal::*;
+use crate::lock::{self, LOCK_FILE, Lock};
+use crate::meta::*;
+#[cfg(feature = "notify")]
+use crate::notify::*;
+use crate::package::*;
+use crate::policy::{self, Hold, InUse};
+use crate::provider::*;
+use crate::release::*;
+use crate::terminal::*;
+use crate::vars::*;
+use crate::vendor::*;
+use crate::verify::*;
+use anyhow::anyhow;
+use semver::Version;
+use std::cmp::Ordering;
+use std::env;
+use std::fs::{self, File};
+use std::path::{self, Path, PathBuf};
+use std::rc::Rc;
+use std::time::Duration;
+use time::OffsetDateTime;
+use tracing::trace;
+
+/// The installation contains everything to materialise a java package (JDK or JRE) to disc.
+#[derive(Debug)]
+pub(crate) struct Installation {
+    config: Rc<InstallationConfig>,
+    dry_run: bool,
+    #[cfg(feature = "direct")]
+    file: String,
+    force: bool,
+    libc: String,
+    #[cfg(feature = "manifest")]
+    manifest: String,
+    os: String,
+    path: PathBuf,
+    protected_paths: Vec<PathBuf>,
+    provider: &'static dyn VendorProvider,
+    vendor: Vendor,
+    wait_lock: Option<Duration>,
+}
+
+impl Installation {
+    // Creates a new [Installation] out of the given [InstallationConfig] backed by the given [VendorProvider].
+    pub(crate) fn from_config(basedir: &Path, config: Rc<InstallationConfig>, provider: &'static dyn VendorProvider) -> Self {
+        let path = basedir.join(InstallationConfig::expand_directory(&config));
+        let path = path::absolute(&path).unwrap_or(path);
+        #[cfg(feature = "direct")]
+        let file = resolve_location(basedir, &InstallationConfig::expand_file(&config));
+        #[cfg(feature = "manifest")]
+        let manifest = resolve_location(basedir, &InstallationConfig::expand_manifest(&config));
+        let libc = InstallationConfig::expand_libc(&config);
+        let os = InstallationConfig::expand_os(&config);
+
+        Self {
+            config,
+            dry_run: false,
+            #[cfg(feature = "direct")]
+            file,
+            force: false,
+            libc,
+            #[cfg(feature = "manifest")]
+            manifest,
+            os,
+            path,
+            protected_paths: Vec::new(),
+            provider,
+            vendor: provider.vendor(),
+            wait_lock: None,
+        }
+    }
+
+    /// Whether to perform the installation or not.
+    pub(crate) fn dry_run(&mut self, dry_run: bool) -> &mut Self {
+        self.dry_run = dry_run;
+
+        self
+    }
+
+    /// Whether to clear the installation directory even if it is protected or not managed by java-updater.
+    pub(crate) fn force(&mut self, force: bool) -> &mut Self {
+        self.force = force;
+
+        self
+    }
+
+    /// Sets the path prefixes, that must never be cleared.
+    pub(crate) fn protected_paths(&mut self, protected_paths: &[PathBuf]) -> &mut Self {
+        self.protected_paths = protected_paths.to_vec();
+
+        self
+    }
+
+    /// Sets the timeout to wait for the lock held by another run (doesn't wait, if not set).
+    pub(crate) fn wait_lock(&mut self, wait_lock: Option<Duration>) -> &mut Self {
+        self.wait_lock = wait_lock;
+
+        self
+    }
+
+    // Set up the installation.
+    pub(crate) fn setup(&self) {
+        let path = PATH_COLOR.paint(self.path.to_string_lossy());
+        let _lock = match self.lock() {
+            Ok(lock) => lock,
+            Err(err) => {
+                let err_str = ATTENTION_COLOR.paint(format!("err = {err:?}"));
+                eprintln!("Failed to lock installation at {path}!\r\n\t{err_str}");
+                #[cfg(feature = "notify")]
+                self.notify_on_failure(None, &err);
+                return;
+            }
+        };
+        if let Err(err) = self.recover() {
+            let err_str = ATTENTION_COLOR.paint(format!("err = {err:?}"));
+            eprintln!("Failed to recover interrupted update of installation at {path}!\r\n\t{err_str}");
+            #[cfg(feature = "notify")]
+            self.notify_on_failure(None, &err);
+            return;
+        }
+
+        let metadata = self.load_metadata();
+        let old_version = metadata.as_ref().map(|metadata| metadata.version.clone()).ok();
+        let old_version_str = old_version.as_ref().map_or("n/a".to_string(), ToString::to_string);
+        let old_version_str = INFO_COLOR.paint(old_version_str);
+        println!("Processing installation at {path} [{old_version_str}]");
+
+        match self.setup_inner(metadata.ok()) {
+            Ok((_, Some(hold))) => {
+                let held_version = INFO_COLOR.paint(hold.version.to_string());
+                let reason = ATTENTION_COLOR.paint(&hold.reason);
+                if self.dry_run {
+                    let not = ATTENTION_COLOR.paint("NOT");
+                    println!("dry-run: {not} processing installation at {path} [{old_version_str} \u{2192} {held_version}] held back: {reason}");
+                } else {
+                    println!("Held back installation at {path} [{old_version_str} \u{2192} {held_version}]: {reason}");
+                    #[cfg(feature = "notify")]
+                    if let Some(old_version) = old_version.as_ref() {
+                        self.notify_on_success(Some(old_version), old_version, Some(&hold));
+                    }
+                }
+            }
+            Ok((Some(metadata), None)) => {
+                let old_version = old_version.as_ref();
+                let new_version = &metadata.version;
+                if old_version != Some(new_version) {
+                    let new_version = INFO_COLOR.paint(new_version.to_string());
+                    if self.dry_run {
+                        let not = ATTENTION_COLOR.paint("NOT");
+                        println!("dry-run: {not} processing installation at {path} [{old_version_str} \u{2192} {new_version}]");
+                    } else {
+                        println!("Processed installation at {path} [{old_version_str} \u{2192} {new_version}]");
+                        #[cfg(feature = "notify")]
+                        self.notify_on_update(old_version, &metadata.version);
+                        #[cfg(feature = "notify")]
+                        self.notify_on_success(old_version, &metadata.version, None);
+                    }
+                } else if self.dry_run {
+                    let not = ATTENTION_COLOR.paint("NOT");
+                    println!("dry-run: {not} processing installation at {path} [{old_version_str}]");
+                } else {
+                    println!("Processed installation at {path} [{old_version_str}]");
+                    #[cfg(feature = "notify")]
+                    self.notify_on_success(old_version, &metadata.version, None);
+                }
+            }
+            Ok((None, None)) => {
+                let version = INFO_COLOR.paint("n/a");
+                if self.dry_run {
+                    let not = ATTENTION_COLOR.paint("NOT");
+                    println!("dry-run: {not} processing installation at {path} [{version}]");
+                } else {
+                    println!("Processed installation at {path} [{version}]");
+                }
+            }
+            Err(err) => {
+                let err_str = ATTENTION_COLOR.paint(format!("err = {err:?}"));
+                eprintln!("Failed to process installation at {path}!\r\n\t{err_str}");
+                #[cfg(feature = "notify")]
+                self.notify_on_failure(old_version.as_ref(), &err);
+            }
+        }
+    }
+
+    // Set up the installation internally.
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn setup_inner(&self, metadata: Option<Metadata>) -> anyhow::Result<(Option<Metadata>, Option<Hold>)> {
+        let request = self.request();
+        let latest = self.query_latest(&request)?;
+        let companions = self.companions(&latest)?;
+        let download = if let Some(ref metadata) = metadata
+            && self.vendor.is_pinned()
+        {
+            // the configured build replaces the installed one, even if it is older (the checksum of adopted builds is unknown)
+            if metadata.checksum.is_empty() {
+                latest.version != metadata.version
+            } else {
+                latest.checksum != metadata.checksum || companions != metadata.companions
+            }
+        } else if let Some(ref metadata) = metadata {
+            // early-access versions (e.g. 24.0.0-ea.20) order by their build number and before the final release
+            match latest.version.cmp_precedence(&metadata.version) {
+                Ordering::Greater => true,
+                Ordering::Less => {
+                    // downgrade only if the installed version does not fulfill the version requirement (anymore)
+                    let downgrade = !request.matches(&metadata.version);
+                    trace!(downgrade, installed = %metadata.version, latest = %latest.version, "newer version installed");
+                    downgrade
+                }
+                // the checksum of adopted builds is unknown
+                Ordering::Equal => !metadata.checksum.is_empty() && (latest.checksum != metadata.checksum || companions != metadata.companions),
+            }
+        } else {
+            true
+        };
+
+        let metadata = if download {
+            // skip builds that have been rolled back
+            if metadata.as_ref().is_some_and(|metadata| metadata.skipped.contains(&latest.version)) {
+                let reason = format!("build {} has been rolled back", latest.version);
+                return Ok((
+                    metadata,
+                    Some(Hold {
+                        reason,
+                        version: latest.version,
+                    }),
+                ));
+            }
+
+            // apply update policy
+            let installed = metadata.as_ref().map(|metadata| &metadata.version);
+            if let Some(hold) = policy::evaluate(&self.config.policy, installed, &latest.version, latest.release_date, OffsetDateTime::now_utc())? {
+                trace!(path = %self.path.display(), ?hold, "held back by policy");
+                return Ok((metadata, Some(hold)));
+            }
+
+            // apply in-use policy
+            if let Some(hold) = self.check_in_use(&latest.version)? {
+                trace!(path = %self.path.display(), ?hold, "held back as in use");
+                return Ok((metadata, Some(hold)));
+            }
+
+            let mut new_metadata = Metadata::new(self.vendor.id(), latest.version, &latest.checksum);
+            new_metadata.archive_format = Some(ArchiveFormat::from_url(&latest.url).unwrap_or_else(|| request.archive_format()));
+            new_metadata.companions = companions;
+            if let Some(metadata) = metadata {
+                // remember the replaced build (its package is still cached) to be able to roll back
+                if !metadata.checksum.is_empty() && metadata.checksum != new_metadata.checksum {
+                    new_metadata.previous = Some(PreviousBuild {
+                        archive_format: metadata.archive_format,
+                        checksum: metadata.checksum,
+                        companions: metadata.companions,
+                        version: metadata.version,
+                    });
+                }
+                new_metadata.skipped = metadata.skipped;
+            }
+            let metadata = new_metadata;
+
+            self.check_clearable()?;
+
+            if self.dry_run {
+                return Ok((Some(metadata), None));
+            }
+
+            // download/unpack the package
+            let urls = metadata.companions.iter().map(|companion| {
+                let url = latest
+                    .companions
+                    .iter()
+                    .find(|latest| latest.kind == companion.kind)
+                    .map(|latest| latest.url.as_str());
+                (companion, url.unwrap_or_default())
+            });
+            let mut package = self.package(&latest.url, &metadata.version, &latest.checksum, metadata.archive_format, urls);
+            package.checksum_algorithm(latest.checksum_algorithm).provide(&metadata)?;
+
+            Some(metadata)
+        } else {
+            trace!(path = %self.path.display(), "no download necessary");
+            metadata
+        };
+
+        Ok((metadata, None))
+    }
+
+    // Whether the versioned (side-by-side) layout is configured.
+    fn is_versioned(&self) -> bool {
+        self.config.layout.trim().eq_ignore_ascii_case("versioned")
+    }
+
+    // Whether the installation is for another operating system or C library than the one of the host (i.e. it can't
+    // be run here).
+    fn is_foreign_platform(&self) -> bool {
+        let os = self.os.trim();
+        let libc = self.libc.trim();
+        (!os.is_empty() && !os.eq_ignore_ascii_case(env::consts::OS)) || (!libc.is_empty() && !libc.eq_ignore_ascii_case(host_libc()))
+    }
+
+    // Checks, that the installation directory may be cleared (i.e. it is not protected and either empty, managed by
+    // java-updater or adopted).
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn check_clearable(&self) -> anyhow::Result<()> {
+        // the versioned layout never clears the installation directory
+        if self.force || self.is_versioned() {
+            return Ok(());
+        }
+
+        if let Some(protected_path) = self
+            .protected_paths
+            .iter()
+            .find(|protected_path| self.path.starts_with(protected_path) || protected_path.starts_with(&self.path))
+        {
+            return Err(anyhow!(
+                "refusing to clear {}, because it is protected by {} (use --force to override)",
+                self.path.display(),
+                protected_path.display()
+            ));
+        }
+
+        let metadata_dir = self.path.join(METADATA_DIR);
+        let empty = fs::read_dir(&self.path).map_or(true, |mut entries| {
+            entries.all(|entry| entry.is_ok_and(|entry| entry.file_name() == METADATA_DIR))
+        });
+        if empty || Metadata::load(metadata_dir.join(METADATA_FILE)).is_ok() || metadata_dir.join(ADOPTED_FILE).exists() {
+            return Ok(());
+        }
+
+        Err(anyhow!(
+            "refusing to clear non-empty directory {}, because it is not managed by java-updater (adopt it or use --force to override)",
+            self.path.display()
+        ))
+    }
+
+    // Checks, whether the installation is in use by running processes and applies the in-use policy (returns the
+    // decision to hold back the given build, if any).
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn check_in_use(&self, version: &Version) -> anyhow::Result<Option<Hold>> {
+        // the versioned layout never replaces a build in use and builds for other platforms aren't run here
+        if self.is_versioned() || self.is_foreign_platform() {
+            return Ok(None);
+        }
+
+        let in_use = policy::in_use(&self.config.policy)?;
+        let mut processes = inuse::processes_using(&self.path);
+        if processes.is_empty() {
+            return Ok(None);
+        }
+
+        let path = PATH_COLOR.paint(self.path.to_string_lossy());
+        let list = |processes: &[inuse::Process]| processes.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ");
+        match in_use {
+            InUse::Proceed => {
+                let list = ATTENTION_COLOR.paint(list(&processes));
+                println!("Proceeding although installation at {path} is in use by {list}");
+                return Ok(None);
+            }
+            InUse::Wait(timeout) if !self.dry_run => {
+                let timeout_str = humantime::format_duration(timeout);
+                let list = ATTENTION_COLOR.paint(list(&processes));
+                println!("Waiting up to {timeout_str} for installation at {path} to be no longer in use by {list}");
+                processes = inuse::wait_until_unused(&self.path, timeout, inuse::WAIT_INTERVAL);
+                if processes.is_empty() {
+                    return Ok(None);
+                }
+            }
+            _ => {}
+        }
+
+        Ok(Some(Hold {
+            reason: format!("installation is in use by {}", list(&processes)),
+            version: version.clone(),
+        }))
+    }
+
+    // Returns the companion artefacts of the given build as configured.
+    fn companions(&self, latest: &MetadataResponse) -> anyhow::Result<Vec<CompanionBuild>> {
+        self.config
+            .companions
+            .iter()
+            .map(|config| {
+                let kind = config.kind();
+                // the presence of the companions has been checked by the query already
+                let Some(companion) = latest.companions.iter().find(|companion| companion.kind == kind) else {
+                    return Err(anyhow!("companion {kind} is not provided by {}", self.vendor.name()));
+                };
+                Ok(CompanionBuild {
+                    archive_format: ArchiveFormat::from_url(&companion.url),
+                    checksum: companion.checksum.clone(),
+                    directory: config.directory(),
+                    kind,
+                })
+            })
+            .collect()
+    }
+
+    // Returns the package of the given build (along with its companions and their URLs), installed side-by-side if
+    // the versioned layout is configured.
+    fn package<'a>(
+        &self,
+        url: &str,
+        version: &Version,
+        checksum: &str,
+        archive_format: Option<ArchiveFormat>,
+        companions: impl IntoIterator<Item = (&'a CompanionBuild, &'a str)>,
+    ) -> Package {
+        let archive_format = archive_format.unwrap_or_else(|| self.request().archive_format());
+        let mut package = Package::new(&self.path, archive_format, url, checksum);
+        for (companion, url) in companions {
+            let companion_format = companion.archive_format.unwrap_or(archive_format);
+            package.companion(Companion::new(&companion.directory, companion_format, url, &companion.checksum));
+        }
+        if self.is_foreign_platform() {
+            trace!(os = self.os, libc = self.libc, "skipping verification of build for foreign platform");
+        } else {
+            let mut verification = Verification::new(&self.config.architecture);
+            verification
+                .version(version.clone())
+                .smoke_test(self.config.smoke_test.as_ref().map(SmokeTest::from_config));
+            package.verification(verification);
+        }
+        if self.is_versioned() {
+            package.versioned(format!("{version}-{checksum}"), self.config.retention);
+        }
+
+        package
+    }
+
+    // Roll back the installation.
+    pub(crate) fn rollback(&self) -> anyhow::Result<()> {
+        let (old, new) = self
+            .rollback_inner()
+            .map_err(|err| err.context(format!("failed to roll back installation at {}", self.path.to_string_lossy())))?;
+        let path = PATH_COLOR.paint(self.path.to_string_lossy());
+        let old_version = INFO_COLOR.paint(old.version.to_string());
+        let new_version = INFO_COLOR.paint(new.version.to_string());
+        if self.dry_run {
+            let not = ATTENTION_COLOR.paint("NOT");
+            println!("dry-run: {not} rolling back installation at {path} [{old_version} \u{2192} {new_version}]");
+        } else {
+            println!("Rolled back installation at {path} [{old_version} \u{2192} {new_version}]");
+            #[cfg(feature = "notify")]
+            self.notify_on_rollback(&old.version, &new.version);
+        }
+
+        Ok(())
+    }
+
+    // Roll back the installation internally and return the old and the new (restored) metadata.
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn rollback_inner(&self) -> anyhow::Result<(Metadata, Metadata)> {
+        let _lock = self.lock()?;
+        self.recover()?;
+        let metadata = self.load_metadata()?;
+        let Some(previous) = metadata.previous.clone() else {
+            return Err(anyhow!("no previous build to roll back to"));
+        };
+
+        // the package of the previous build must still be cached (or installed side-by-side)
+        let checksum_algorithm = ChecksumAlgorithm::from_checksum(&previous.checksum);
+        let companions = previous.companions.iter().map(|companion| (companion, ""));
+        let mut package = self.package("", &previous.version, &previous.checksum, previous.archive_format, companions);
+        package.checksum_algorithm(checksum_algorithm);
+        if !package.is_available() {
+            return Err(anyhow!("package of previous build {} not cached anymore", previous.version));
+        }
+
+        // restore previous build and skip the rolled back build in future runs
+        let mut restored = Metadata::new(self.vendor.id(), previous.version, &previous.checksum);
+        restored.archive_format = previous.archive_format;
+        restored.companions.clone_from(&previous.companions);
+        restored.skipped.clone_from(&metadata.skipped);
+        if !restored.skipped.contains(&metadata.version) {
+            restored.skipped.push(metadata.version.clone());
+        }
+
+        self.check_clearable()?;
+        if let Some(hold) = self.check_in_use(&restored.version)? {
+            return Err(anyhow!(hold.reason));
+        }
+
+        if self.dry_run {
+            return Ok((metadata, restored));
+        }
+
+        package.provide(&restored)?;
+
+        Ok((metadata, restored))
+    }
+
+    // Adopt the (unmanaged) installation.
+    pub(crate) fn adopt(&self) -> anyhow::Result<()> {
+        let metadata = self
+            .adopt_inner()
+            .map_err(|err| err.context(format!("failed to adopt installation at {}", self.path.to_string_lossy())))?;
+        let path = PATH_COLOR.paint(self.path.to_string_lossy());
+        let version = INFO_COLOR.paint(metadata.version.to_string());
+        if self.dry_run {
+            let not = ATTENTION_COLOR.paint("NOT");
+            println!("dry-run: {not} adopting installation at {path} [{version}]");
+        } else {
+            println!("Adopted installation at {path} [{version}]");
+        }
+
+        Ok(())
+    }
+
+    // Adopt the installation internally and return the metadata inferred from its release file.
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn adopt_inner(&self) -> anyhow::Result<Metadata> {
+        let _lock = self.lock()?;
+        let metadata_dir = self.path.join(METADATA_DIR);
+        let filename = metadata_dir.join(METADATA_FILE);
+        if filename.exists() {
+            return Err(anyhow!("installation is already managed by java-updater"));
+        }
+
+        let metadata = self.release_metadata()?;
+        if self.dry_run {
+            return Ok(metadata);
+        }
+
+        fs::create_dir_all(&metadata_dir)?;
+        metadata.save(filename)?;
+        File::create(metadata_dir.join(ADOPTED_FILE))?;
+
+        Ok(metadata)
+    }
+
+    // Returns the root directory of the installed build.
+    fn java_home(&self) -> PathBuf {
+        if self.is_versioned() {
+            self.path.join(CURRENT_LINK)
+        } else {
+            self.path.clone()
+        }
+    }
+
+    // Infers the metadata (without checksum) from the release file of the installed build.
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn release_metadata(&self) -> anyhow::Result<Metadata> {
+        let release = Release::load(self.java_home().join(RELEASE_FILE))?;
+        if let Some(vendor) = Vendor::from_implementor(&release.implementor)
+            && vendor != self.vendor
+            && !self.vendor.is_aggregator()
+        {
+            return Err(anyhow!(
+                "vendors differ (expected: {}, got: {} from release file)",
+                self.vendor.id(),
+                vendor.id()
+            ));
+        }
+        let version = release.version()?;
+
+        Ok(Metadata::new(self.vendor.id(), version, ""))
+    }
+
+    // Load local metadata (or infer it from the release file of an unmanaged installation).
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn load_metadata(&self) -> anyhow::Result<Metadata> {
+        let filename = self.path.join(METADATA_DIR).join(METADATA_FILE);
+        if !filename.exists() {
+            return self.release_metadata();
+        }
+        let metadata = Metadata::load(filename)?;
+        if self.vendor.id() != metadata.vendor {
+            return Err(anyhow!("vendors differ (expected: {}, got: {})", self.vendor.id(), metadata.vendor));
+        }
+
+        Ok(metadata)
+    }
+
+    // Query latest metadata that fulfills the given request.
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn query_latest(&self, request: &MetadataRequest) -> anyhow::Result<MetadataResponse> {
+        request.version_req()?;
+        if request.is_javafx() && !self.vendor.provides_javafx() {
+            return Err(anyhow!("package type {} is not provided by {}", request.package_variant(), self.vendor.name()));
+        }
+        if request.libc().as_deref() == Some("musl") && !self.vendor.provides_musl() {
+            return Err(anyhow!("C library musl is not provided by {}", self.vendor.name()));
+        }
+        let response = self.provider.query(request)?;
+        if let Some(kind) = request
+            .companions
+            .iter()
+            .find(|kind| !response.companions.iter().any(|companion| &companion.kind == *kind))
+        {
+            return Err(anyhow!("companion {kind} is not provided by {}", self.vendor.name()));
+        }
+
+        Ok(response)
+    }
+
+    // Build the request out of the configuration.
+    fn request(&self) -> MetadataRequest {
+        MetadataRequest {
+            #[cfg(feature = "github-release")]
+            api_url: self.config.api_url.clone(),
+            #[cfg(any(
+                feature = "azul",
+                feature = "corretto",
+                feature = "disco",
+                feature = "eclipse",
+                feature = "liberica",
+                feature = "manifest",
+                feature = "microsoft",
+                feature = "sapmachine",
+                feature = "semeru"
+            ))]
+            arch: self.config.architecture.clone(),
+            #[cfg(feature = "github-release")]
+            asset: InstallationConfig::expand_asset(&self.config),
+            #[cfg(feature = "liberica")]
+            bundle: self.config.bundle.clone(),
+            #[cfg(any(
+                feature = "azul",
+                feature = "corretto",
+                feature = "disco",
+                feature = "eclipse",
+                feature = "github-release",
+                feature = "liberica",
+                feature = "manifest",
+                feature = "microsoft",
+                feature = "sapmachine",
+                feature = "semeru"
+            ))]
+            channel: self.config.channel.clone(),
+            #[cfg(feature = "github-release")]
+            checksum_asset: InstallationConfig::expand_checksum_asset(&self.config),
+            companions: self.config.companions.iter().map(CompanionConfig::kind).collect(),
+            #[cfg(feature = "disco")]
+            distribution: self.config.distribution.clone(),
+            #[cfg(feature = "direct")]
+            file: self.file.clone(),
+            #[cfg(any(feature = "eclipse", feature = "semeru"))]
+            jvm_impl: self.config.jvm_impl.clone(),
+            libc: self.libc.clone(),
+            #[cfg(feature = "manifest")]
+            manifest: self.manifest.clone(),
+            os: self.os.clone(),
+            package_type: self.config.package_type.clone(),
+            #[cfg(feature = "direct")]
+            release: self.config.release.clone(),
+            #[cfg(feature = "github-release")]
+            repository: self.config.repository.clone(),
+            #[cfg(feature = "direct")]
+            sha256: self.config.sha256.clone(),
+            #[cfg(feature = "direct")]
+            url: InstallationConfig::expand_url(&self.config),
+            version: self.config.version.clone(),
+        }
+    }
+
+    // Locks the installation to prevent concurrent runs (not necessary in dry-run mode).
+    fn lock(&self) -> anyhow::Result<Option<Lock>> {
+        if self.dry_run {
+            return Ok(None);
+        }
+
+        let filename = self.path.join(METADATA_DIR).join(LOCK_FILE);
+        lock::acquire(&filename, self.wait_lock).map(Some)
+    }
+
+    // Recovers an interrupted update (completes or reverts it), if there is one.
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn recover(&self) -> anyhow::Result<()> {
+        let filename = Journal::filename(&self.path);
+        if !filename.exists() {
+            return Ok(());
+        }
+
+        let journal = Journal::load(&filename)?;
+        let path = PATH_COLOR.paint(self.path.to_string_lossy());
+        let version = INFO_COLOR.paint(journal.metadata.version.to_string());
+        if self.dry_run {
+            let not = ATTENTION_COLOR.paint("NOT");
+            println!("dry-run: {not} recovering interrupted update of installation at {path} [{version}]");
+            return Ok(());
+        }
+
+        let checksum_algorithm = ChecksumAlgorithm::from_checksum(&journal.metadata.checksum);
+        let metadata = &journal.metadata;
+        let companions = metadata.companions.iter().map(|companion| (companion, ""));
+        let mut package = self.package("", &metadata.version, &metadata.checksum, metadata.archive_format, companions);
+        package.checksum_algorithm(checksum_algorithm);
+        match package.recover(&journal)? {
+            Recovery::Completed => println!("Completed interrupted update of installation at {path} [{version}]"),
+            Recovery::Reverted => println!("Reverted interrupted update of installation at {path} [{version}]"),
+        }
+
+        Ok(())
+    }
+
+    // Notify in case of failure.
+    #[cfg(feature = "notify")]
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn notify_on_failure(&self, old: Option<&semver::Version>, err: &anyhow::Error) {
+        let mut vars = vec![(ENV_JU_ERROR, err.to_string())];
+        if let Some(old) = old {
+            vars.push((ENV_JU_OLD_VERSION, old.to_string()));
+        }
+        self.notify("on-failure", &self.config.on_failure, NotifyKind::Failure, vars);
+    }
+
+    // Notify in case of success.
+    #[cfg(feature = "notify")]
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn notify_on_success(&self, old: Option<&semver::Version>, new: &semver::Version, hold: Option<&Hold>) {
+        let mut vars = Vec::new();
+        if let Some(hold) = hold {
+            vars.push((ENV_JU_HELD_REASON, hold.reason.clone()));
+            vars.push((ENV_JU_HELD_VERSION, hold.version.to_string()));
+        }
+        vars.push((ENV_JU_NEW_VERSION, new.to_string()));
+        if let Some(old) = old {
+            vars.push((ENV_JU_OLD_VERSION, old.to_string()));
+        }
+        self.notify("on-success", &self.config.on_success, NotifyKind::Success, vars);
+    }
+
+    // Notify in case of update.
+    #[cfg(feature = "notify")]
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn notify_on_update(&self, old: Option<&semver::Version>, new: &semver::Version) {
+        let mut vars = vec![(ENV_JU_NEW_VERSION, new.to_string())];
+        if let Some(old) = old {
+            vars.push((ENV_JU_OLD_VERSION, old.to_string()));
+        }
+        self.notify("on-update", &self.config.on_update, NotifyKind::Success, vars);
+    }
+
+    // Notify in case of rollback.
+    #[cfg(feature = "notify")]
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn notify_on_rollback(&self, old: &semver::Version, new: &semver::Version) {
+        let vars = vec![(ENV_JU_NEW_VERSION, new.to_string()), (ENV_JU_OLD_VERSION, old.to_string())];
+        self.notify("on-rollback", &self.config.on_rollback, NotifyKind::Rollback, vars);
+    }
+
+    // Executes the given commands with the common variables and the given additional variables, which are made
+    // available to the executed program via its environment as well.
+    #[cfg(feature = "notify")]
+    fn notify(&self, hook: &str, commands: &[NotifyCommandConfig], kind: NotifyKind, vars: Vec<(&'static str, String)>) {
+        if commands.is_empty() {
+            return;
+        }
+
+        // collect common and additional variables
+        let mut all_vars = vec![
+            (ENV_JU_ARCH, env::consts::ARCH.to_string()),
+            (ENV_JU_CONFIG_ARCH, self.config.architecture.clone()),
+            (ENV_JU_CONFIG_CHANNEL, self.config.channel.clone()),
+            (ENV_JU_CONFIG_DIRECTORY, self.config.directory.clone()),
+            (ENV_JU_CONFIG_LIBC, self.libc.clone()),
+            (ENV_JU_CONFIG_OS, self.os.clone()),
+            (ENV_JU_CONFIG_TYPE, self.config.package_type.clone()),
+            (ENV_JU_CONFIG_VENDOR, self.config.vendor.clone()),
+            (ENV_JU_CONFIG_VERSION, self.config.version.clone()),
+            (ENV_JU_DIRECTORY, self.path.to_string_lossy().to_string()),
+            (ENV_JU_FAMILY, env::consts::FAMILY.to_string()),
+            (ENV_JU_OS, env::consts::OS.to_string()),
+            (ENV_JU_TYPE, self.config.package_type.clone()),
+            (ENV_JU_VENDOR_ID, self.vendor.id().to_string()),
+            (ENV_JU_VENDOR_NAME, self.vendor.name().to_string()),
+        ];
+        all_vars.extend(vars);
+
+        // setup variable resolver(s)
+        let mut simple_var_resolver = SimpleVarResolver::new();
+        for (name, value) in &all_vars {
+            simple_var_resolver.insert(*name, value.clone());
+        }
+        let env_var_resolver = PrefixedVarResolver::new("env.", Rc::new(OsEnvVarResolver));
+        let var_resolvers: Vec<Rc<dyn VarResolver>> = vec![Rc::new(simple_var_resolver), Rc::new(env_var_resolver)];
+        let var_expander = VarExpander::new(var_resolvers);
+
+        // process all commands
+        for command in commands {
+            // setup command
+            let mut command = NotifyCommand::from_config(command);
+            command.kind(kind.clone());
+            for (name, value) in &all_vars {
+                command.env(name, value);
+            }
+
+            // execute command
+            trace!(?command, "executing {hook} command");
+            command.execute(&var_expander);
+        }
+    }
+}
+
+// Resolves the given location (URL or path) against the given base directory.
+#[cfg(any(feature = "direct", feature = "manifest"))]
+fn resolve_location(basedir: &Path, location: &str) -> String {
+    let location = location.trim();
+    if location.is_empty() || location.contains("://") {
+        return location.to_string();
+    }
+
+    let path = basedir.join(location);
+    let path = path::absolute(&path).unwrap_or(path);
+    path.to_string_lossy().to_string()
+}
+
+#[cfg(all(test, feature = "direct"))]
+mod tests {
+
+    use super::*;
+    use tempfile::tempdir;
+    use test_log::test;
+
+    // Creates an installation (for the direct vendor) in the given directory.
+    fn installation(basedir: &Path, directory: &str) -> Installation {
+        let config = InstallationConfig {
+            directory: directory.to_string(),
+            vendor: "direct".to_string(),
+            ..Default::default()
+        };
+        let provider = provider(Vendor::Direct).unwrap();
+        Installation::from_config(basedir, Rc::new(config), provider)
+    }
+
+    #[test]
+    fn clearable() {
+        let tempdir = tempdir().unwrap();
+        let basedir = tempdir.path();
+        let mut installation = installation(basedir, "jdk");
+
+        // missing or empty directory
+        assert!(installation.check_clearable().is_ok());
+        fs::create_dir_all(basedir.join("jdk").join(METADATA_DIR)).unwrap();
+        assert!(installation.check_clearable().is_ok());
+
+        // foreign content
+        File::create(basedir.join("jdk").join("important.txt")).unwrap();
+        assert!(installation.check_clearable().is_err());
+        assert!(installation.force(true).check_clearable().is_ok());
+        installation.force(false);
+
+        // adopted
+        File::create(basedir.join("jdk").join(METADATA_DIR).join(ADOPTED_FILE)).unwrap();
+        assert!(installation.check_clearable().is_ok());
+
+        // protected
+        installation.protected_paths(&[basedir.to_path_buf()]);
+        assert!(installation.check_clearable().is_err());
+        installation.protected_paths(&[basedir.join("jdk").join("lib")]);
+        assert!(installation.check_clearable().is_err());
+        installation.protected_paths(&[basedir.join("other")]);
+        assert!(installation.check_clearable().is_ok());
+    }
+
+    #[test]
+    fn adopt() {
+        let tempdir = tempdir().unwrap();
+        let basedir = tempdir.path();
+        let installation = installation(basedir, "jdk");
+
+        // nothing to adopt
+        assert!(installation.load_metadata().is_err());
+        assert!(installation.adopt_inner().is_err());
+
+        // infer metadata from release file
+        fs::create_dir_all(basedir.join("jdk")).unwrap();
+        let release = "IMPLEMENTOR=\"Eclipse Adoptium\"\nJAVA_RUNTIME_VERSION=\"21.0.5+11-LTS\"\nJAVA_VERSION=\"21.0.5\"\n";
+        fs::write(basedir.join("jdk").join(RELEASE_FILE), release).unwrap();
+        assert_eq!(Metadata::new("direct", Version::new(21, 0, 5), ""), installation.load_metadata().unwrap());
+
+        // adopt
+        let metadata = installation.adopt_inner().unwrap();
+        assert_eq!(Version::new(21, 0, 5), metadata.version);
+        assert!(basedir.join("jdk").join(METADATA_DIR).join(ADOPTED_FILE).exists());
+        assert_eq!(metadata, installation.load_metadata().unwrap());
+        assert!(installation.adopt_inner().is_err());
+    }
+
+    #[test]
+    fn pinned_downgrade() {
+        let tempdir = tempdir().unwrap();
+        let config = InstallationConfig {
+            directory: "jdk".to_string(),
+            release: "17.0.8".to_string(),
+            sha256: "abcd".to_string(),
+            url: "https://example.com/jdk-17.0.8.tar.gz".to_string(),
+            vendor: "direct".to_string(),
+            ..Default::default()
+        };
+        let provider = provider(Vendor::Direct).unwrap();
+        let mut installation = Installation::from_config(tempdir.path(), Rc::new(config), provider);
+        installation.dry_run(true);
+
+        // the configured (older) build replaces the installed one
+        let installed = Metadata::new("direct", Version::new(17, 0, 9), "ef01");
+        let (metadata, hold) = installation.setup_inner(Some(installed)).unwrap();
+        assert!(hold.is_none());
+        assert_eq!(Some(Version::new(17, 0, 8)), metadata.map(|metadata| metadata.version));
+
+        // unchanged configuration is a no-op
+        let installed = Metadata::new("direct", Version::new(17, 0, 8), "abcd");
+        let (metadata, _) = installation.setup_inner(Some(installed.clone())).unwrap();
+        assert_eq!(Some(installed), metadata);
+    }
+
+    #[cfg(feature = "corretto")]
+    #[test]
+    fn musl_not_provided() {
+        let tempdir = tempdir().unwrap();
+        let config = InstallationConfig {
+            directory: "jdk".to_string(),
+            libc: "musl".to_string(),
+            os: "linux".to_string(),
+            vendor: "corretto".to_string(),
+            version: "21".to_string(),
+            ..Default::default()
+        };
+        let provider = provider(Vendor::Corretto).unwrap();
+        let installation = Installation::from_config(tempdir.path(), Rc::new(config), provider);
+
+        // rejected before querying the vendor
+        let err = installation.query_latest(&installation.request()).unwrap_err();
+        assert_eq!("C library musl is not provided by Amazon (Corretto)", err.to_string());
+    }
+}
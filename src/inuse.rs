@@ -0,0 +1,177 @@
+//! In-use detection.
+//!
+//! This module contains the detection of processes running from (or using files of) an installation directory.
+
+use std::fmt;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+use tracing::trace;
+
+/// Interval between two checks while waiting for an installation to be no longer in use.
+pub(crate) const WAIT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A process using an installation.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Process {
+    /// The command line of the process (or a description, if unknown)
+    pub(crate) cmdline: String,
+    /// The id of the process, if known
+    pub(crate) pid: Option<u32>,
+}
+
+impl fmt::Display for Process {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.pid {
+            Some(pid) => write!(f, "PID {pid} ({})", self.cmdline),
+            None => write!(f, "{}", self.cmdline),
+        }
+    }
+}
+
+/// Returns the processes running from (or having mapped files of) the given directory.
+#[cfg(target_os = "linux")]
+#[tracing::instrument(level = "trace", ret)]
+pub(crate) fn processes_using(path: &Path) -> Vec<Process> {
+    use std::fs;
+
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+
+    let own_pid = std::process::id();
+    let mut processes = Vec::new();
+    for entry in entries.filter_map(Result::ok) {
+        let Some(pid) = entry.file_name().to_str().and_then(|pid| pid.parse::<u32>().ok()) else {
+            continue;
+        };
+        if pid == own_pid {
+            continue;
+        }
+
+        // executable (e.g. bin/java) or mapped files (e.g. lib/server/libjvm.so)
+        let proc_dir = entry.path();
+        let exe = fs::read_link(proc_dir.join("exe")).is_ok_and(|exe| exe.starts_with(path));
+        if !exe && !fs::read_to_string(proc_dir.join("maps")).is_ok_and(|maps| maps_contain(&maps, path)) {
+            continue;
+        }
+
+        let cmdline = fs::read(proc_dir.join("cmdline")).unwrap_or_default();
+        let cmdline = String::from_utf8_lossy(&cmdline).replace('\0', " ").trim().to_string();
+        processes.push(Process { cmdline, pid: Some(pid) });
+    }
+
+    processes
+}
+
+/// Returns a placeholder process, if the given directory is locked (i.e. its `lib` directory can't be renamed).
+#[cfg(windows)]
+#[tracing::instrument(level = "trace", ret)]
+pub(crate) fn processes_using(path: &Path) -> Vec<Process> {
+    use std::fs;
+
+    let lib = path.join("lib");
+    if !lib.exists() {
+        return Vec::new();
+    }
+
+    let mut lib_renamed = path.join("lib");
+    lib_renamed.set_extension("in-use");
+    if let Err(err) = fs::rename(&lib, &lib_renamed) {
+        trace!(?err, "installation is locked");
+        return vec![Process {
+            cmdline: "unknown process".to_string(),
+            pid: None,
+        }];
+    }
+
+    // revert rename
+    let _ = fs::rename(lib_renamed, lib);
+
+    Vec::new()
+}
+
+/// Returns no processes, as the detection is not supported on this platform.
+#[cfg(not(any(target_os = "linux", windows)))]
+pub(crate) fn processes_using(_path: &Path) -> Vec<Process> {
+    Vec::new()
+}
+
+/// Waits (at most the given timeout) for the given directory to be no longer in use and returns the processes still
+/// using it.
+pub(crate) fn wait_until_unused(path: &Path, timeout: Duration, interval: Duration) -> Vec<Process> {
+    let start = Instant::now();
+    loop {
+        let processes = processes_using(path);
+        if processes.is_empty() || start.elapsed() >= timeout {
+            return processes;
+        }
+
+        trace!(?processes, "waiting for installation to be no longer in use");
+        thread::sleep(interval.min(timeout.saturating_sub(start.elapsed())));
+    }
+}
+
+// Checks, whether the given content of `/proc/<pid>/maps` contains a file within the given directory.
+#[cfg(target_os = "linux")]
+#[doc(hidden)]
+fn maps_contain(maps: &str, path: &Path) -> bool {
+    // address perms offset dev inode pathname (the pathname is padded and may contain spaces)
+    maps.lines()
+        .filter_map(|line| line.splitn(6, char::is_whitespace).nth(5))
+        .map(str::trim_start)
+        .any(|pathname| Path::new(pathname).starts_with(path))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn display() {
+        let process = Process {
+            cmdline: "java -jar app.jar".to_string(),
+            pid: Some(42),
+        };
+        assert_eq!("PID 42 (java -jar app.jar)", process.to_string());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn maps() {
+        let maps = "\
+7f0000000000-7f0000001000 r-xp 00000000 08:01 1234 /opt/java/21/lib/server/libjvm.so
+7f0000002000-7f0000003000 rw-p 00000000 00:00 0
+7f0000004000-7f0000005000 r--p 00000000 08:01 5678                       /usr/lib/libc.so.6
+7f0000006000-7f0000007000 r--p 00000000 08:01 9012                       /opt/my java/17/lib/modules";
+        assert!(maps_contain(maps, Path::new("/opt/java/21")));
+        assert!(maps_contain(maps, Path::new("/opt/my java/17")));
+        assert!(!maps_contain(maps, Path::new("/opt/java/17")));
+        assert!(!maps_contain(maps, Path::new("/opt/java/2")));
+        assert!(!maps_contain(maps, Path::new("/opt/my")));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn running_process() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let sleep = tempdir.path().join("sleep");
+        std::fs::copy("/bin/sleep", &sleep).unwrap();
+        let mut child = std::process::Command::new(&sleep).arg("10").spawn().unwrap();
+
+        // wait for the child to exec
+        for _ in 0..100 {
+            if !processes_using(tempdir.path()).is_empty() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        let processes = wait_until_unused(tempdir.path(), Duration::from_millis(50), Duration::from_millis(10));
+        child.kill().unwrap();
+        child.wait().unwrap();
+        assert_eq!(Some(child.id()), processes.first().and_then(|process| process.pid));
+        assert!(processes_using(tempdir.path()).is_empty());
+    }
+}
@@ -0,0 +1,113 @@
+//! Transaction journal.
+//!
+//! This module contains the journal recording the phases of an update, so an interrupted update can be completed or
+//! reverted by the next run.
+
+use crate::meta::*;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+/// Name of the journal file within the metadata directory.
+pub(crate) const JOURNAL_FILE: &str = "journal";
+
+/// The phases of an update (in order).
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum Phase {
+    /// The package has been downloaded (and verified).
+    Downloaded,
+    /// The package has been unpacked to the tmp directory (and verified).
+    Unpacked,
+    /// The old installation has been moved aside.
+    OldMovedAside,
+    /// The new installation has been moved in.
+    NewMovedIn,
+    /// The metadata of the new installation has been written.
+    Committed,
+}
+
+/// The outcome of the recovery of an interrupted update.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Recovery {
+    /// The interrupted update has been completed.
+    Completed,
+    /// The interrupted update has been reverted.
+    Reverted,
+}
+
+/// Struct to hold the journal of an update.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Journal {
+    /// The metadata to be written on commit
+    pub(crate) metadata: Metadata,
+    /// The last completed phase
+    pub(crate) phase: Phase,
+}
+
+impl Journal {
+    /// Returns the filename of the journal within the given installation directory.
+    pub(crate) fn filename(path: &Path) -> PathBuf {
+        path.join(METADATA_DIR).join(JOURNAL_FILE)
+    }
+
+    /// Loads the `Journal` from the given filename.
+    #[tracing::instrument(err(level = "trace"), level = "trace")]
+    pub(crate) fn load<P>(filename: P) -> Result<Self>
+    where
+        P: AsRef<Path> + std::fmt::Debug,
+    {
+        let journal_file = File::open(filename)?;
+        let journal = serde_yaml::from_reader(journal_file)?;
+
+        Ok(journal)
+    }
+
+    /// Saves the `Journal` to the given filename (atomically by writing a tmp file first).
+    #[tracing::instrument(err(level = "trace"), level = "trace")]
+    pub(crate) fn save<P>(&self, filename: P) -> Result<()>
+    where
+        P: AsRef<Path> + std::fmt::Debug,
+    {
+        let filename = filename.as_ref();
+        let mut tmp = filename.to_path_buf();
+        tmp.set_extension("tmp");
+        let journal_file = File::create(&tmp)?;
+        serde_yaml::to_writer(&journal_file, self)?;
+        journal_file.sync_all()?;
+        fs::rename(tmp, filename)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use semver::Version;
+    use tempfile::tempdir;
+    use test_log::test;
+
+    #[test]
+    fn save_and_load() {
+        let tempdir = tempdir().unwrap();
+        let file = tempdir.path().join(JOURNAL_FILE);
+        let journal = Journal {
+            metadata: Metadata::new("whatever", Version::new(1, 2, 3), "abcd"),
+            phase: Phase::OldMovedAside,
+        };
+        journal.save(&file).unwrap();
+        assert_eq!(journal, Journal::load(&file).unwrap());
+    }
+
+    #[test]
+    fn phase_order() {
+        assert!(Phase::Downloaded < Phase::Unpacked);
+        assert!(Phase::Unpacked < Phase::OldMovedAside);
+        assert!(Phase::OldMovedAside < Phase::NewMovedIn);
+        assert!(Phase::NewMovedIn < Phase::Committed);
+    }
+}
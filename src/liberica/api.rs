@@ -0,0 +1,181 @@
+use super::*;
+use crate::checksum::ChecksumAlgorithm;
+use anyhow::anyhow;
+use reqwest::Url;
+use semver::Version;
+use serde::Deserialize;
+use tracing::trace;
+
+// Query the API for all relevant data.
+pub(super) fn query(request: &MetadataRequest) -> anyhow::Result<MetadataResponse> {
+    let url = query_url(request)?;
+    trace!(url = url.as_str());
+    let client = reqwest::blocking::Client::new();
+    let response = client
+        .get(url) //
+        .header(reqwest::header::ACCEPT, "application/json") //
+        .send()?;
+    let bytes = response.bytes()?;
+    let mut de = serde_json::Deserializer::from_slice(&bytes);
+    let response: serde_json::Value = Deserialize::deserialize(&mut de)?;
+    trace!("response = {response:#?}");
+
+    // check structure of response
+
+    let Some(response) = response.as_array() else {
+        return Err(anyhow!("response has not the expected structure"));
+    };
+    let bundle_type = bundle_type(request);
+    let (version, response) = response
+        .iter()
+        .filter(|r| r["bundleType"].as_str() == Some(bundle_type.as_str()))
+        .filter_map(|r| version(request, r).ok().map(|version| (version, r)))
+        .filter(|(version, _)| request.matches(version))
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .ok_or_else(|| anyhow!("no package found for bundle {bundle_type}"))?;
+
+    // url
+
+    let Some(url) = response["downloadUrl"].as_str() else {
+        return Err(anyhow!("field 'downloadUrl' not present in response"));
+    };
+
+    // checksum
+
+    let Some(checksum) = response["sha1"].as_str() else {
+        return Err(anyhow!("field 'sha1' not present in response"));
+    };
+
+    Ok(MetadataResponse {
+        checksum: checksum.to_lowercase(),
+        checksum_algorithm: ChecksumAlgorithm::Sha1,
+        companions: Vec::new(),
+        release_date: None,
+        url: url.to_string(),
+        version,
+    })
+}
+
+// Returns the version of the given package.
+fn version(request: &MetadataRequest, response: &serde_json::Value) -> anyhow::Result<Version> {
+    let Some(major) = response["featureVersion"].as_u64() else {
+        return Err(anyhow!("field 'featureVersion' not present in response"));
+    };
+    let Some(minor) = response["interimVersion"].as_u64() else {
+        return Err(anyhow!("field 'interimVersion' not present in response"));
+    };
+    let Some(patch) = response["updateVersion"].as_u64() else {
+        return Err(anyhow!("field 'updateVersion' not present in response"));
+    };
+    let mut version = Version::new(major, minor, patch);
+    if request.is_early_access() {
+        if let Some(build) = response["buildVersion"].as_u64() {
+            version.build = semver::BuildMetadata::new(&build.to_string())?;
+        }
+        version = early_access(version);
+    }
+
+    Ok(version)
+}
+
+// Build the query URL to search for packages.
+fn query_url(request: &MetadataRequest) -> anyhow::Result<Url> {
+    let (arch, bitness) = arch(request)?;
+    let mut url = Url::parse(API_URL)?;
+    url.query_pairs_mut()
+        .append_pair("arch", arch)
+        .append_pair("bitness", bitness)
+        .append_pair("bundle-type", &bundle_type(request))
+        .append_pair("installation-type", "archive")
+        .append_pair("os", &request.os())
+        .append_pair("package-type", request.archive_format().ext())
+        .append_pair("release-type", &request.channel())
+        .append_pair("version-feature", &request.version());
+    if request.is_latest() {
+        url.query_pairs_mut().append_pair("version-modifier", "latest");
+    }
+
+    Ok(url)
+}
+
+// Returns the requested architecture and its bitness for the package, named as used by the API.
+fn arch(request: &MetadataRequest) -> anyhow::Result<(&'static str, &'static str)> {
+    let arch = request.arch_name(Vendor::Liberica)?;
+    let bitness = request.arch()?.bits();
+
+    Ok((arch, bitness))
+}
+
+// Returns the requested bundle type (package type combined with bundle) for the package, JavaFX requires the full bundle.
+fn bundle_type(request: &MetadataRequest) -> String {
+    let package_type = request.package_type();
+    match request.bundle().as_str() {
+        _ if request.is_javafx() => format!("{package_type}-full"),
+        "full" => format!("{package_type}-full"),
+        "lite" => format!("{package_type}-lite"),
+        _ => package_type,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn test_normalize_aarch64_architecture() {
+        let request = MetadataRequest {
+            arch: "aarch64".to_string(),
+            os: "linux".to_string(),
+            package_type: "jdk".to_string(),
+            version: "21".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(("arm", "64"), arch(&request).unwrap());
+    }
+
+    #[test]
+    fn test_bundle_type_full() {
+        let request = MetadataRequest {
+            bundle: "Full".to_string(),
+            package_type: "jre".to_string(),
+            ..Default::default()
+        };
+        assert_eq!("jre-full", bundle_type(&request));
+    }
+
+    #[test]
+    fn test_bundle_type_javafx() {
+        let request = MetadataRequest {
+            package_type: "jdk+fx".to_string(),
+            ..Default::default()
+        };
+        assert_eq!("jdk-full", bundle_type(&request));
+    }
+
+    #[test]
+    fn test_bundle_type_standard() {
+        let request = MetadataRequest {
+            package_type: "jdk".to_string(),
+            ..Default::default()
+        };
+        assert_eq!("jdk", bundle_type(&request));
+    }
+
+    #[test]
+    fn test_query_url() {
+        let request = MetadataRequest {
+            arch: "x86_64".to_string(),
+            bundle: "lite".to_string(),
+            os: "linux".to_string(),
+            package_type: "jdk".to_string(),
+            version: "17".to_string(),
+            ..Default::default()
+        };
+        let url = query_url(&request).unwrap();
+        let query = url.query().unwrap_or_default();
+        assert!(query.contains("arch=x86&bitness=64&bundle-type=jdk-lite"));
+        assert!(query.contains("version-feature=17"));
+    }
+}
@@ -0,0 +1,29 @@
+//! Liberica.
+//!
+//! This module contains the implementation to query java packages from BellSoft (Liberica).
+
+// https://api.bell-sw.com/api.html
+
+#[doc(hidden)]
+mod api;
+
+use crate::provider::*;
+use crate::vendor::*;
+
+// Base URL for the API endpoint.
+#[doc(hidden)]
+const API_URL: &str = "https://api.bell-sw.com/v1/liberica/releases";
+
+/// The [`VendorProvider`] for BellSoft (Liberica).
+#[derive(Debug)]
+pub(crate) struct LibericaProvider;
+
+impl VendorProvider for LibericaProvider {
+    fn vendor(&self) -> Vendor {
+        Vendor::Liberica
+    }
+
+    fn query(&self, request: &MetadataRequest) -> anyhow::Result<MetadataResponse> {
+        api::query(request)
+    }
+}
@@ -0,0 +1,106 @@
+//! Lock.
+//!
+//! This module contains the advisory file locks preventing concurrent runs (on the same installation).
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::{Seek, Write};
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+use tracing::trace;
+
+/// Name of the lock file within the metadata directory.
+pub(crate) const LOCK_FILE: &str = "lock";
+
+// Interval between two attempts while waiting for a lock.
+#[doc(hidden)]
+const WAIT_INTERVAL: Duration = Duration::from_millis(500);
+
+/// An acquired lock, released when dropped.
+#[derive(Debug)]
+pub(crate) struct Lock {
+    // The locked file (the lock is released when the file is closed).
+    _file: File,
+}
+
+/// Struct to hold the owner of a lock (written into the lock file).
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+struct Owner {
+    /// The id of the process holding the lock
+    pid: u32,
+    /// The date and time (RFC 3339) since the lock is held
+    since: String,
+}
+
+/// Acquires the lock on the given file, waiting at most the given timeout (if any) for the current owner to release it.
+#[tracing::instrument(level = "trace")]
+pub(crate) fn acquire(path: &Path, wait: Option<Duration>) -> anyhow::Result<Lock> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).truncate(false).read(true).write(true).open(path)?;
+
+    let start = Instant::now();
+    loop {
+        match file.try_lock() {
+            Ok(()) => break,
+            Err(TryLockError::WouldBlock) => {
+                if wait.is_none_or(|wait| start.elapsed() >= wait) {
+                    return Err(anyhow!("{} is locked by {}", path.display(), owner(path)));
+                }
+                trace!(path = %path.display(), "waiting for lock");
+                thread::sleep(WAIT_INTERVAL);
+            }
+            Err(TryLockError::Error(err)) => return Err(err.into()),
+        }
+    }
+
+    // record the owner
+    let owner = Owner {
+        pid: std::process::id(),
+        since: OffsetDateTime::now_utc().format(&Rfc3339)?,
+    };
+    file.set_len(0)?;
+    file.rewind()?;
+    file.write_all(serde_yaml::to_string(&owner)?.as_bytes())?;
+    file.flush()?;
+
+    Ok(Lock { _file: file })
+}
+
+// Returns a description of the owner of the given lock file.
+#[doc(hidden)]
+fn owner(path: &Path) -> String {
+    match fs::read_to_string(path).ok().and_then(|content| serde_yaml::from_str::<Owner>(&content).ok()) {
+        Some(owner) => format!("PID {} since {}", owner.pid, owner.since),
+        None => "unknown process".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use tempfile::tempdir;
+    use test_log::test;
+
+    #[test]
+    fn exclusive() {
+        let tempdir = tempdir().unwrap();
+        let path = tempdir.path().join(".java-updater").join(LOCK_FILE);
+
+        let lock = acquire(&path, None).unwrap();
+        let err = acquire(&path, Some(Duration::from_millis(10))).unwrap_err();
+        let pid = std::process::id();
+        assert!(err.to_string().contains(&format!("locked by PID {pid} since ")), "{err}");
+
+        // released on drop
+        drop(lock);
+        assert!(acquire(&path, None).is_ok());
+    }
+}
@@ -1,27 +1,72 @@
+mod arch;
+mod archive;
 mod args;
 #[cfg(feature = "azul")]
 mod azul;
 mod checksum;
 mod config;
+#[cfg(feature = "corretto")]
+mod corretto;
+#[cfg(feature = "direct")]
+mod direct;
+#[cfg(feature = "disco")]
+mod disco;
 #[cfg(feature = "eclipse")]
 mod eclipse;
+#[cfg(any(feature = "github-release", feature = "sapmachine", feature = "semeru"))]
+mod github;
+#[cfg(feature = "github-release")]
+mod github_release;
+mod installation;
+mod inuse;
+mod journal;
+#[cfg(feature = "liberica")]
+mod liberica;
+mod lock;
+#[cfg(feature = "manifest")]
+mod manifest;
 mod meta;
+#[cfg(feature = "microsoft")]
+mod microsoft;
 #[cfg(feature = "notify")]
 mod notify;
 mod package;
+mod policy;
+mod provider;
+mod release;
+#[cfg(feature = "sapmachine")]
+mod sapmachine;
+#[cfg(feature = "semeru")]
+mod semeru;
 mod terminal;
 mod vars;
 mod vendor;
+mod verify;
 mod version;
 
-#[cfg(not(any(feature = "azul", feature = "eclipse")))]
+#[cfg(not(any(
+    feature = "azul",
+    feature = "corretto",
+    feature = "direct",
+    feature = "disco",
+    feature = "eclipse",
+    feature = "github-release",
+    feature = "liberica",
+    feature = "manifest",
+    feature = "microsoft",
+    feature = "sapmachine",
+    feature = "semeru"
+)))]
 compile_error!("At least one vendor must be set.");
 
 use crate::args::*;
 use crate::config::*;
+use crate::installation::*;
+use crate::provider::*;
 use crate::terminal::*;
 use crate::vendor::*;
 use crate::version::*;
+use anyhow::anyhow;
 use clap::Parser;
 use std::path::{self, Path, PathBuf};
 use std::rc::Rc;
@@ -100,6 +145,31 @@ fn internal_main() -> anyhow::Result<()> {
     };
     debug!(basedir = %basedir.display());
 
+    // prevent concurrent runs of the same configuration
+    let _lock = if args.dry_run {
+        None
+    } else {
+        let mut lock_path = config_path.clone().into_os_string();
+        lock_path.push(".lock");
+        // a blocked (e.g. scheduled) run must not look like a successful one
+        match lock::acquire(Path::new(&lock_path), wait_lock(&args)) {
+            Ok(lock) => Some(lock),
+            Err(err) => return Err(err.context("failed to lock configuration")),
+        }
+    };
+
+    // adopt or roll back a single installation
+    match args.command {
+        Some(Command::Adopt { ref directory }) => {
+            return adopt(basedir, &args, config.installations, directory);
+        }
+        Some(Command::Rollback { ref directory }) => {
+            let protected_paths = config.protected_paths(basedir);
+            return rollback(basedir, &args, &protected_paths, config.installations, directory);
+        }
+        None => {}
+    }
+
     // start processing installations
     let thread_pool = ThreadPool::new(num_threads(args.threads));
     let args = Arc::new(args);
@@ -110,13 +180,15 @@ fn internal_main() -> anyhow::Result<()> {
     set_windows_progress(Some(0));
 
     let processed = Arc::new(AtomicUsize::new(0));
+    let protected_paths = Arc::new(config.protected_paths(basedir));
     for installation in config.installations {
         let basedir = basedir.to_path_buf();
         let args = args.clone();
+        let protected_paths = protected_paths.clone();
         let processed = processed.clone();
         thread_pool.execute(move || {
             // setup installation
-            setup(&basedir, &args, Rc::new(installation));
+            setup(&basedir, &args, &protected_paths, Rc::new(installation));
 
             // update window title
             let i = processed.fetch_add(1, Ordering::Relaxed) + 1;
@@ -142,6 +214,12 @@ fn internal_main() -> anyhow::Result<()> {
     Ok(())
 }
 
+// Returns the timeout to wait for locks held by other runs (if any).
+#[doc(hidden)]
+fn wait_lock(args: &Args) -> Option<Duration> {
+    args.wait_lock.map(|timeout| timeout.unwrap_or(Duration::MAX))
+}
+
 // Factor to compute the threads.
 const THREADS_FACTOR: usize = 2;
 
@@ -216,7 +294,7 @@ fn init_tracing(args: &Args) {
 }
 
 // Set up installation.
-fn setup(basedir: &Path, args: &Args, config: Rc<InstallationConfig>) {
+fn setup(basedir: &Path, args: &Args, protected_paths: &[PathBuf], config: Rc<InstallationConfig>) {
     let path = basedir.join(InstallationConfig::expand_directory(&config));
     let path = path::absolute(&path).unwrap_or(path);
     let path = PATH_COLOR.paint(path.to_string_lossy());
@@ -235,10 +313,73 @@ fn setup(basedir: &Path, args: &Args, config: Rc<InstallationConfig>) {
     };
     trace!(?vendor);
 
-    match vendor {
-        #[cfg(feature = "azul")]
-        Vendor::Azul => azul::setup(basedir, args, config),
-        #[cfg(feature = "eclipse")]
-        Vendor::Eclipse => eclipse::setup(basedir, args, config),
+    let Some(provider) = provider(vendor) else {
+        let not = ATTENTION_COLOR.paint("NOT");
+        println!("{not} processing installation at {path} \u{2192} no provider for vendor '{vendor}'");
+        return;
+    };
+
+    let mut installation = Installation::from_config(basedir, config, provider);
+
+    installation //
+        .dry_run(args.dry_run) //
+        .force(args.force) //
+        .protected_paths(protected_paths) //
+        .wait_lock(wait_lock(args)) //
+        .setup();
+}
+
+// Roll back the installation(s) in the given directory.
+fn rollback(basedir: &Path, args: &Args, protected_paths: &[PathBuf], installations: Vec<InstallationConfig>, directory: &str) -> anyhow::Result<()> {
+    let installations = installations_in(basedir, installations, directory);
+    if installations.is_empty() {
+        return Err(anyhow!("no installation configured at {directory}"));
+    }
+
+    for mut installation in installations {
+        installation //
+            .dry_run(args.dry_run) //
+            .force(args.force) //
+            .protected_paths(protected_paths) //
+            .wait_lock(wait_lock(args)) //
+            .rollback()?;
+    }
+
+    Ok(())
+}
+
+// Adopt the (unmanaged) installation(s) in the given directory.
+fn adopt(basedir: &Path, args: &Args, installations: Vec<InstallationConfig>, directory: &str) -> anyhow::Result<()> {
+    let installations = installations_in(basedir, installations, directory);
+    if installations.is_empty() {
+        return Err(anyhow!("no installation configured at {directory}"));
+    }
+
+    for mut installation in installations {
+        installation //
+            .dry_run(args.dry_run) //
+            .wait_lock(wait_lock(args)) //
+            .adopt()?;
     }
+
+    Ok(())
+}
+
+// Returns the configured installation(s) in the given directory (absolute or relative to the base directory).
+fn installations_in(basedir: &Path, installations: Vec<InstallationConfig>, directory: &str) -> Vec<Installation> {
+    let candidates = [path::absolute(directory).ok(), path::absolute(basedir.join(directory)).ok()];
+    installations
+        .into_iter()
+        .map(Rc::new)
+        .filter(|config| {
+            let path = basedir.join(InstallationConfig::expand_directory(config));
+            let path = path::absolute(&path).unwrap_or(path);
+            candidates.contains(&Some(path))
+        })
+        .filter_map(|config| {
+            let vendor = Vendor::try_from(config.vendor.as_str()).ok()?;
+            let provider = provider(vendor)?;
+            Some(Installation::from_config(basedir, config, provider))
+        })
+        .collect()
 }
@@ -0,0 +1,225 @@
+use super::*;
+use crate::arch::Arch;
+use crate::checksum::ChecksumAlgorithm;
+use crate::package::local_path;
+use anyhow::anyhow;
+use reqwest::Url;
+use serde::Deserialize;
+use std::fs::File;
+use std::path;
+use tracing::trace;
+
+/// The manifest listing all available packages.
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    /// List with packages.
+    #[serde(default)]
+    packages: Vec<ManifestPackage>,
+}
+
+/// A package within the manifest.
+#[derive(Debug, Deserialize)]
+struct ManifestPackage {
+    /// The architecture of the package.
+    arch: String,
+    /// The operating system of the package.
+    os: String,
+    /// The checksum (SHA256) of the package.
+    sha256: String,
+    /// The type of the package (JDK or JRE).
+    #[serde(rename = "type")]
+    package_type: String,
+    /// The release date of the package.
+    #[serde(default, rename = "release-date")]
+    release_date: Option<String>,
+    /// The (absolute or relative) URL of the package.
+    url: String,
+    /// The version of the package.
+    version: String,
+}
+
+// Query the manifest for all relevant data.
+pub(super) fn query(request: &MetadataRequest) -> anyhow::Result<MetadataResponse> {
+    let location = location(request)?;
+    let manifest = load(&location)?;
+    trace!(?manifest);
+
+    let arch = request.arch()?;
+    let os = request.os();
+    let package_type = request.package_variant();
+    let (version, package) = manifest
+        .packages
+        .iter()
+        .filter(|p| Arch::try_from(p.arch.as_str()) == Ok(arch) && os == p.os.trim().to_lowercase() && package_type == p.package_type.trim().to_lowercase())
+        .filter_map(|p| parse_version(&p.version).ok().map(|version| (version, p)))
+        .filter(|(version, _)| request.matches(version))
+        .filter(|(version, _)| version.pre.is_empty() || request.is_early_access())
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .ok_or_else(|| anyhow!("no package found for {os}/{arch}/{package_type}/{}", request.version))?;
+
+    // url (relative to the manifest)
+
+    let url = location.join(package.url.trim())?;
+
+    Ok(MetadataResponse {
+        checksum: package.sha256.trim().to_lowercase(),
+        checksum_algorithm: ChecksumAlgorithm::Sha256,
+        companions: Vec::new(),
+        release_date: package.release_date.as_deref().and_then(parse_release_date),
+        url: url.to_string(),
+        version,
+    })
+}
+
+// Returns the location of the manifest as URL.
+fn location(request: &MetadataRequest) -> anyhow::Result<Url> {
+    let manifest = request.manifest.trim();
+    if manifest.is_empty() {
+        return Err(anyhow!("no manifest configured"));
+    }
+
+    if let Ok(url) = Url::parse(manifest)
+        && matches!(url.scheme(), "file" | "http" | "https")
+    {
+        return Ok(url);
+    }
+
+    let path = path::absolute(manifest)?;
+    Url::from_file_path(&path).map_err(|()| anyhow!("invalid manifest location {}", path.display()))
+}
+
+// Loads the manifest from the given location.
+fn load(location: &Url) -> anyhow::Result<Manifest> {
+    trace!(location = location.as_str());
+    let manifest = if let Some(path) = local_path(location.as_str()) {
+        let file = File::open(path)?;
+        serde_yaml::from_reader(file)?
+    } else {
+        let client = reqwest::blocking::Client::new();
+        let response = client
+            .get(location.clone()) //
+            .send()?
+            .error_for_status()?;
+        let bytes = response.bytes()?;
+        serde_yaml::from_slice(&bytes)?
+    };
+
+    Ok(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use semver::Version;
+    use std::io::Write;
+    use tempfile::tempdir;
+    use test_log::test;
+
+    const MANIFEST: &str = r#"
+        packages:
+          - { version: "21.0.1", os: linux, arch: x64, type: jdk, url: 21.0.1/jdk.tar.gz, sha256: AAAA }
+          - { version: "21.0.2", os: linux, arch: x64, type: jdk, url: 21.0.2/jdk.tar.gz, sha256: BBBB }
+          - { version: "21.0.3", os: linux, arch: aarch64, type: jdk, url: 21.0.3/jdk.tar.gz, sha256: CCCC }
+          - { version: "22.0.1", os: linux, arch: x64, type: jdk, url: 22.0.1/jdk.tar.gz, sha256: DDDD }
+          - { version: "23-ea+5", os: linux, arch: x64, type: jdk, url: 23-ea+5/jdk.tar.gz, sha256: FFFF }
+          - { version: "21.0.4", os: windows, arch: x64, type: jdk, url: https://mirror/21.0.4/jdk.zip, sha256: EEEE }
+        "#;
+
+    #[test]
+    fn test_query_newest_matching() {
+        let tempdir = tempdir().unwrap();
+        let file = tempdir.path().join("manifest.yml");
+        File::create(&file).unwrap().write_all(MANIFEST.as_bytes()).unwrap();
+
+        let request = MetadataRequest {
+            arch: "x86_64".to_string(),
+            manifest: file.to_string_lossy().to_string(),
+            os: "linux".to_string(),
+            package_type: "jdk".to_string(),
+            version: "21".to_string(),
+            ..Default::default()
+        };
+        let response = query(&request).unwrap();
+        assert_eq!("bbbb", response.checksum);
+        assert_eq!(Version::new(21, 0, 2), response.version);
+        let expected = Url::from_file_path(tempdir.path().join("21.0.2").join("jdk.tar.gz")).unwrap();
+        assert_eq!(expected.as_str(), response.url);
+    }
+
+    #[test]
+    fn test_query_absolute_url() {
+        let tempdir = tempdir().unwrap();
+        let file = tempdir.path().join("manifest.json");
+        File::create(&file).unwrap().write_all(MANIFEST.as_bytes()).unwrap();
+
+        let request = MetadataRequest {
+            arch: "x64".to_string(),
+            manifest: file.to_string_lossy().to_string(),
+            os: "windows".to_string(),
+            package_type: "jdk".to_string(),
+            version: "21".to_string(),
+            ..Default::default()
+        };
+        let response = query(&request).unwrap();
+        assert_eq!("https://mirror/21.0.4/jdk.zip", response.url);
+    }
+
+    #[test]
+    fn test_query_version_requirement() {
+        let tempdir = tempdir().unwrap();
+        let file = tempdir.path().join("manifest.yml");
+        File::create(&file).unwrap().write_all(MANIFEST.as_bytes()).unwrap();
+
+        let mut request = MetadataRequest {
+            arch: "x64".to_string(),
+            manifest: file.to_string_lossy().to_string(),
+            os: "linux".to_string(),
+            package_type: "jdk".to_string(),
+            version: "21.0.1".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(Version::new(21, 0, 1), query(&request).unwrap().version);
+        request.version = ">=21.0.1, <21.0.3".to_string();
+        assert_eq!(Version::new(21, 0, 2), query(&request).unwrap().version);
+        request.version = "21.0.x".to_string();
+        assert_eq!(Version::new(21, 0, 2), query(&request).unwrap().version);
+    }
+
+    #[test]
+    fn test_query_early_access() {
+        let tempdir = tempdir().unwrap();
+        let file = tempdir.path().join("manifest.yml");
+        File::create(&file).unwrap().write_all(MANIFEST.as_bytes()).unwrap();
+
+        let mut request = MetadataRequest {
+            arch: "x64".to_string(),
+            manifest: file.to_string_lossy().to_string(),
+            os: "linux".to_string(),
+            package_type: "jdk".to_string(),
+            version: "23".to_string(),
+            ..Default::default()
+        };
+        assert!(query(&request).is_err());
+        request.channel = "ea".to_string();
+        let response = query(&request).unwrap();
+        assert_eq!(Version::parse("23.0.0-ea.5").unwrap(), response.version);
+    }
+
+    #[test]
+    fn test_query_no_match() {
+        let tempdir = tempdir().unwrap();
+        let file = tempdir.path().join("manifest.yml");
+        File::create(&file).unwrap().write_all(MANIFEST.as_bytes()).unwrap();
+
+        let request = MetadataRequest {
+            arch: "x64".to_string(),
+            manifest: file.to_string_lossy().to_string(),
+            os: "linux".to_string(),
+            package_type: "jre".to_string(),
+            version: "21".to_string(),
+            ..Default::default()
+        };
+        assert!(query(&request).is_err());
+    }
+}
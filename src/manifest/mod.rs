@@ -0,0 +1,37 @@
+//! Manifest.
+//!
+//! This module contains the implementation to query java packages from a manifest (e.g. hosted on an internal mirror).
+//!
+//! The manifest is a YAML (or JSON) document that lists the available packages:
+//!
+//! ```yaml
+//! packages:
+//!   - version: 21.0.1
+//!     os: linux
+//!     arch: x64
+//!     type: jdk
+//!     url: https://mirror.example.com/jdk/21.0.1/linux-x64.tar.gz
+//!     sha256: 0123456789abcdef...
+//! ```
+//!
+//! Relative URLs are resolved against the location of the manifest.
+
+#[doc(hidden)]
+mod api;
+
+use crate::provider::*;
+use crate::vendor::*;
+
+/// The [`VendorProvider`] for manifests.
+#[derive(Debug)]
+pub(crate) struct ManifestProvider;
+
+impl VendorProvider for ManifestProvider {
+    fn vendor(&self) -> Vendor {
+        Vendor::Manifest
+    }
+
+    fn query(&self, request: &MetadataRequest) -> anyhow::Result<MetadataResponse> {
+        api::query(request)
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! This module contains the installation metadata read from a file within the installation directory.
 
+use crate::archive::ArchiveFormat;
 use anyhow::Result;
 use semver::Version;
 use serde::{Deserialize, Serialize};
@@ -15,21 +16,71 @@ pub(crate) const METADATA_DIR: &str = ".java-updater";
 /// Name of the metadata file within the metadata directory.
 pub(crate) const METADATA_FILE: &str = "meta";
 
+/// Name of the marker file within the metadata directory for adopted installations.
+pub(crate) const ADOPTED_FILE: &str = "adopted";
+
 /// Struct to hold the metadata for an installation.
-#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(deny_unknown_fields)]
 pub(crate) struct Metadata {
+    /// The archive format of the downloaded package (defaults to the one of the host's operating system)
+    #[serde(default, rename = "archive-format", skip_serializing_if = "default")]
+    pub(crate) archive_format: Option<ArchiveFormat>,
     /// The checksum of the downloaded package
     pub(crate) checksum: String,
+    /// The companion artefacts (e.g. debug image) installed alongside the build
+    #[serde(default, skip_serializing_if = "default")]
+    pub(crate) companions: Vec<CompanionBuild>,
+    /// Whether the installation is a pre-release (early-access) build
+    #[serde(default, skip_serializing_if = "default")]
+    pub(crate) prerelease: bool,
+    /// The previously installed build (used for rollbacks)
+    #[serde(default, skip_serializing_if = "default")]
+    pub(crate) previous: Option<PreviousBuild>,
     /// Additional properties
     #[serde(default, skip_serializing_if = "default")]
     pub(crate) props: HashMap<String, String>,
+    /// The versions of the builds that have been rolled back and must not be installed again
+    #[serde(default, skip_serializing_if = "default")]
+    pub(crate) skipped: Vec<Version>,
     /// The vendor of the installation (Azul, Eclipse, etc.)
     pub(crate) vendor: String,
     /// The version of the installation
     pub(crate) version: Version,
 }
 
+/// Struct to hold the previously installed build.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct PreviousBuild {
+    /// The archive format of the downloaded package (defaults to the one of the host's operating system)
+    #[serde(default, rename = "archive-format", skip_serializing_if = "default")]
+    pub(crate) archive_format: Option<ArchiveFormat>,
+    /// The checksum of the downloaded package (also the name of the cached package)
+    pub(crate) checksum: String,
+    /// The companion artefacts (e.g. debug image) installed alongside the build
+    #[serde(default, skip_serializing_if = "default")]
+    pub(crate) companions: Vec<CompanionBuild>,
+    /// The version of the build
+    pub(crate) version: Version,
+}
+
+/// Struct to hold a companion artefact (e.g. debug image) installed alongside a build.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct CompanionBuild {
+    /// The archive format of the downloaded artefact (defaults to the one of the host's operating system)
+    #[serde(default, rename = "archive-format", skip_serializing_if = "default")]
+    pub(crate) archive_format: Option<ArchiveFormat>,
+    /// The checksum of the downloaded artefact (also the name of the cached artefact)
+    pub(crate) checksum: String,
+    /// The directory (relative to the installation) the artefact is unpacked into
+    pub(crate) directory: String,
+    /// The type of the artefact (e.g. debugimage)
+    #[serde(rename = "type")]
+    pub(crate) kind: String,
+}
+
 // Helper to determine if the given field has the default value.
 #[doc(hidden)]
 fn default<F: Default + PartialEq>(f: &F) -> bool {
@@ -40,8 +91,13 @@ impl Metadata {
     /// Creates a new `Metadata`.
     pub(crate) fn new(vendor: impl Into<String>, version: Version, checksum: impl Into<String>) -> Self {
         Self {
+            archive_format: None,
             checksum: checksum.into(),
+            companions: Vec::new(),
+            prerelease: !version.pre.is_empty(),
+            previous: None,
             props: HashMap::new(),
+            skipped: Vec::new(),
             vendor: vendor.into(),
             version,
         }
@@ -92,9 +148,31 @@ mod tests {
 
         // test
         let mut md = Metadata::new("whatever", Version::parse("1.2.3").unwrap(), "abcd".to_string());
+        md.archive_format = Some(ArchiveFormat::Zip);
         md.props.insert("k".to_string(), "v".to_string());
+        md.companions.push(CompanionBuild {
+            archive_format: Some(ArchiveFormat::TarGz),
+            checksum: "ijkl".to_string(),
+            directory: "debugimage".to_string(),
+            kind: "debugimage".to_string(),
+        });
+        md.previous = Some(PreviousBuild {
+            archive_format: None,
+            checksum: "efgh".to_string(),
+            companions: Vec::new(),
+            version: Version::parse("1.2.2").unwrap(),
+        });
+        md.skipped.push(Version::parse("1.2.4").unwrap());
         md.save(&file).unwrap();
         let md_loaded = Metadata::load(&file).unwrap();
         assert_eq!(md, md_loaded);
     }
+
+    #[test]
+    fn prerelease() {
+        let md = Metadata::new("whatever", Version::parse("24.0.0-ea.20").unwrap(), "abcd".to_string());
+        assert!(md.prerelease);
+        let md = Metadata::new("whatever", Version::parse("21.0.1+12").unwrap(), "abcd".to_string());
+        assert!(!md.prerelease);
+    }
 }
@@ -0,0 +1,133 @@
+use super::*;
+use crate::checksum::ChecksumAlgorithm;
+use anyhow::anyhow;
+use reqwest::Url;
+use semver::Version;
+use tracing::trace;
+
+// Prefix of all package filenames.
+#[doc(hidden)]
+const FILENAME_PREFIX: &str = "microsoft-jdk-";
+
+// Query the permanent download links for all relevant data.
+pub(super) fn query(request: &MetadataRequest) -> anyhow::Result<MetadataResponse> {
+    if request.package_type() != "jdk" {
+        return Err(anyhow!("package type {} not available", request.package_type()));
+    }
+    if request.is_early_access() {
+        return Err(anyhow!("early-access builds not available"));
+    }
+
+    let filename = filename(request)?;
+
+    // resolve the permanent link to the versioned download link
+    let url = Url::parse(DOWNLOAD_URL)?.join(&filename)?;
+    trace!(url = url.as_str());
+    let client = reqwest::blocking::Client::new();
+    let response = client
+        .head(url) //
+        .send()?
+        .error_for_status()?;
+    let url = response.url().clone();
+    trace!(resolved_url = url.as_str());
+
+    // version
+
+    let version = version_from_url(&url)?;
+    // only the latest version is available via the permanent download links
+    if !request.matches(&version) {
+        return Err(anyhow!("latest version {version} does not fulfill version requirement {}", request.version));
+    }
+
+    // checksum (published as "<checksum>  <filename>")
+
+    let checksum_url = Url::parse(DOWNLOAD_URL)?.join(&format!("{filename}.sha256sum.txt"))?;
+    trace!(checksum_url = checksum_url.as_str());
+    let response = client
+        .get(checksum_url) //
+        .header(reqwest::header::ACCEPT, "text/plain") //
+        .send()?
+        .error_for_status()?;
+    let checksum = response.text()?;
+    let Some(checksum) = checksum.split_whitespace().next() else {
+        return Err(anyhow!("checksum not present in response"));
+    };
+
+    Ok(MetadataResponse {
+        checksum: checksum.to_lowercase(),
+        checksum_algorithm: ChecksumAlgorithm::Sha256,
+        companions: Vec::new(),
+        release_date: None,
+        url: url.to_string(),
+        version,
+    })
+}
+
+// Returns the filename of the permanent download link for the package.
+fn filename(request: &MetadataRequest) -> anyhow::Result<String> {
+    let version = request.version();
+    let os = os(request);
+    let arch = arch(request)?;
+    let archive_format = request.archive_format();
+    Ok(format!("{FILENAME_PREFIX}{version}-{os}-{arch}.{archive_format}"))
+}
+
+// Extracts the version out of the resolved download link (.../microsoft-jdk-<version>-<os>-<arch>.<ext>).
+fn version_from_url(url: &Url) -> anyhow::Result<Version> {
+    let version = url
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .and_then(|filename| filename.strip_prefix(FILENAME_PREFIX))
+        .and_then(|filename| filename.split('-').next())
+        .ok_or_else(|| anyhow!("version not present in '{url}'"))?;
+
+    parse_version(version)
+}
+
+// Returns the requested architecture for the package, named as used by the permanent download links.
+fn arch(request: &MetadataRequest) -> anyhow::Result<&'static str> {
+    request.arch_name(Vendor::Microsoft)
+}
+
+// Returns the requested operating system for the package, normalized for the permanent download links.
+fn os(request: &MetadataRequest) -> String {
+    match request.os().as_str() {
+        "darwin" | "mac" | "macosx" => "macos".to_string(),
+        os => os.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn test_filename() {
+        let request = MetadataRequest {
+            arch: "x86_64".to_string(),
+            os: "linux".to_string(),
+            package_type: "jdk".to_string(),
+            version: "21".to_string(),
+            ..Default::default()
+        };
+        assert_eq!("microsoft-jdk-21-linux-x64.tar.gz", filename(&request).unwrap());
+    }
+
+    #[test]
+    fn test_version_from_url() {
+        let url = Url::parse("https://download.visualstudio.microsoft.com/download/pr/1/2/microsoft-jdk-11.0.20.1-linux-x64.tar.gz").unwrap();
+        let version = version_from_url(&url).unwrap();
+        assert_eq!(Version::parse("11.0.20+1").unwrap(), version);
+    }
+
+    #[test]
+    fn test_query_jre() {
+        let request = MetadataRequest {
+            package_type: "jre".to_string(),
+            ..Default::default()
+        };
+        assert!(query(&request).is_err());
+    }
+}
@@ -0,0 +1,29 @@
+//! Microsoft.
+//!
+//! This module contains the implementation to query java packages from Microsoft (Build of OpenJDK).
+
+// https://learn.microsoft.com/en-us/java/openjdk/download
+
+#[doc(hidden)]
+mod api;
+
+use crate::provider::*;
+use crate::vendor::*;
+
+// Base URL for the permanent download links.
+#[doc(hidden)]
+const DOWNLOAD_URL: &str = "https://aka.ms/download-jdk/";
+
+/// The [`VendorProvider`] for Microsoft (Build of OpenJDK).
+#[derive(Debug)]
+pub(crate) struct MicrosoftProvider;
+
+impl VendorProvider for MicrosoftProvider {
+    fn vendor(&self) -> Vendor {
+        Vendor::Microsoft
+    }
+
+    fn query(&self, request: &MetadataRequest) -> anyhow::Result<MetadataResponse> {
+        api::query(request)
+    }
+}
@@ -12,8 +12,14 @@ use tracing::error;
 pub(crate) const ENV_JU_ARCH: &str = "JU_ARCH";
 /// Environment variable holding the configured target architecture.
 pub(crate) const ENV_JU_CONFIG_ARCH: &str = "JU_CONFIG_ARCH";
+/// Environment variable holding the configured release channel.
+pub(crate) const ENV_JU_CONFIG_CHANNEL: &str = "JU_CONFIG_CHANNEL";
 /// Environment variable holding the raw configured directory.
 pub(crate) const ENV_JU_CONFIG_DIRECTORY: &str = "JU_CONFIG_DIRECTORY";
+/// Environment variable holding the configured target C library (expanded).
+pub(crate) const ENV_JU_CONFIG_LIBC: &str = "JU_CONFIG_LIBC";
+/// Environment variable holding the configured target operating system (expanded).
+pub(crate) const ENV_JU_CONFIG_OS: &str = "JU_CONFIG_OS";
 /// Environment variable holding the configured package type.
 pub(crate) const ENV_JU_CONFIG_TYPE: &str = "JU_CONFIG_TYPE";
 /// Environment variable holding the configured vendor.
@@ -26,6 +32,10 @@ pub(crate) const ENV_JU_DIRECTORY: &str = "JU_DIRECTORY";
 pub(crate) const ENV_JU_ERROR: &str = "JU_ERROR";
 /// Environment variable holding the runtime operating-system family.
 pub(crate) const ENV_JU_FAMILY: &str = "JU_FAMILY";
+/// Environment variable holding the reason why an update was held back.
+pub(crate) const ENV_JU_HELD_REASON: &str = "JU_HELD_REASON";
+/// Environment variable holding the Java version held back.
+pub(crate) const ENV_JU_HELD_VERSION: &str = "JU_HELD_VERSION";
 /// Environment variable holding the newly installed Java version.
 pub(crate) const ENV_JU_NEW_VERSION: &str = "JU_NEW_VERSION";
 /// Environment variable holding the previously installed Java version.
@@ -44,6 +54,8 @@ pub(crate) const ENV_JU_VENDOR_NAME: &str = "JU_VENDOR_NAME";
 pub(crate) enum NotifyKind {
     /// Failure
     Failure,
+    /// Rollback
+    Rollback,
     /// Success
     Success,
 }
@@ -94,6 +106,7 @@ impl NotifyCommand {
         if let Err(err) = self.execute_inner(var_expander) {
             match self.kind {
                 Some(NotifyKind::Failure) => error!(?err, "failed to execute notify (on failure) command"),
+                Some(NotifyKind::Rollback) => error!(?err, "failed to execute notify (on rollback) command"),
                 Some(NotifyKind::Success) => error!(?err, "failed to execute notify (on success) command"),
                 None => error!(?err, "failed to execute notify command"),
             }
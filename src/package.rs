@@ -2,162 +2,235 @@
 //!
 //! This module contains the code to download and unpack a java package.
 
-use crate::checksum::{self, ChecksumWrite};
+use crate::archive::{self, ArchiveFormat};
+use crate::checksum::{self, ChecksumAlgorithm, ChecksumWrite};
+use crate::inuse;
+use crate::journal::*;
 use crate::meta::*;
+use crate::verify::Verification;
 use anyhow::anyhow;
+use reqwest::Url;
 use std::ffi::OsStr;
 use std::fs::{self, File};
+use std::io;
 use std::path::{Path, PathBuf};
-use tracing::{error, trace, warn};
+use tracing::{trace, warn};
+
+/// Name of the directory (within the installation directory) containing the side-by-side installed versions.
+pub(crate) const VERSIONS_DIR: &str = "versions";
+
+/// Name of the symlink (within the installation directory) pointing to the current version.
+pub(crate) const CURRENT_LINK: &str = "current";
+
+// Name of the directory (within the metadata directory) the old installation is moved aside to during an update.
+#[doc(hidden)]
+const OLD_DIR: &str = "old";
+
+/// Returns the local path for the given URL, if it is a `file:` URL.
+pub(crate) fn local_path(url: &str) -> Option<PathBuf> {
+    let url = Url::parse(url).ok()?;
+    if url.scheme() != "file" {
+        return None;
+    }
+
+    url.to_file_path().ok()
+}
+
+/// Struct to hold all necessary data to download and unpack a companion artefact (e.g. debug image) alongside a java
+/// package.
+pub(crate) struct Companion {
+    archive_format: ArchiveFormat,
+    checksum: String,
+    directory: String,
+    url: String,
+}
+
+impl Companion {
+    /// Creates a new `Companion` unpacked into the given directory (relative to the installation).
+    pub(crate) fn new(directory: impl Into<String>, archive_format: ArchiveFormat, url: impl Into<String>, checksum: impl Into<String>) -> Self {
+        Self {
+            archive_format,
+            checksum: checksum.into(),
+            directory: directory.into(),
+            url: url.into(),
+        }
+    }
+}
 
 /// Struct to hold all necessary data to download and unpack a java package.
 pub(crate) struct Package {
+    archive_format: ArchiveFormat,
     checksum: String,
-    ext: String,
+    checksum_algorithm: ChecksumAlgorithm,
+    companions: Vec<Companion>,
     path: PathBuf,
+    retention: usize,
     url: String,
+    verification: Option<Verification>,
+    versioned: Option<String>,
 }
 
 impl Package {
     /// Creates a new `Package`.
-    pub(crate) fn new(path: impl Into<PathBuf>, ext: impl Into<String>, url: impl Into<String>, checksum: impl Into<String>) -> Self {
+    pub(crate) fn new(path: impl Into<PathBuf>, archive_format: ArchiveFormat, url: impl Into<String>, checksum: impl Into<String>) -> Self {
         Self {
+            archive_format,
             checksum: checksum.into(),
+            checksum_algorithm: ChecksumAlgorithm::default(),
+            companions: Vec::new(),
             path: path.into(),
+            retention: 0,
             url: url.into(),
-            ext: ext.into(),
+            verification: None,
+            versioned: None,
         }
     }
 
-    /// Provide (download annd unpack) the package.
-    pub(crate) fn provide(&self) -> anyhow::Result<()> {
-        let pkg = self.download()?;
-        self.unpack(&pkg)
+    /// Verifies the unpacked package (by running it) before it replaces the installed one.
+    pub(crate) fn verification(&mut self, verification: Verification) -> &mut Self {
+        self.verification = Some(verification);
+
+        self
     }
 
-    // Download the package.
-    #[tracing::instrument(level = "trace", skip(self))]
-    fn download(&self) -> anyhow::Result<PathBuf> {
-        let metadata_dir = self.path.join(METADATA_DIR);
-        let mut dest = metadata_dir.join(&self.checksum);
-        dest.set_extension(&self.ext);
+    /// Installs the package side-by-side into `versions/<name>` and switches the `current` symlink to it, keeping
+    /// `retention` versions (including the current one).
+    pub(crate) fn versioned(&mut self, name: impl Into<String>, retention: usize) -> &mut Self {
+        self.versioned = Some(name.into());
+        self.retention = retention;
 
-        // check if already downloaded
-        if dest.exists() && checksum::checksum(&dest)? == self.checksum {
-            return Ok(dest.clone());
-        }
+        self
+    }
 
-        // make request
-        let client = reqwest::blocking::Client::new();
-        let mut response = client
-            .get(&self.url) //
-            .header(reqwest::header::ACCEPT, "application/octet-stream") //
-            .send()?;
-
-        // download file
-        fs::create_dir_all(&metadata_dir)?;
-        trace!(pkg = %dest.display());
-        let dest_file = File::create(&dest)?;
-        let mut checksum_write = ChecksumWrite::new(dest_file);
-        let bytes_written = response.copy_to(&mut checksum_write)?;
-        trace!(bytes_written);
-        let checksum_calculated = checksum_write.checksum()?;
-        trace!(checksum_calculated);
-
-        // calculate/verify checksum
-        if self.checksum.to_lowercase() != checksum_calculated {
-            return Err(anyhow::Error::msg("hashes differ"));
-        }
+    /// Adds a companion artefact, that is downloaded and unpacked alongside the package.
+    pub(crate) fn companion(&mut self, companion: Companion) -> &mut Self {
+        self.companions.push(companion);
 
-        Ok(dest.clone())
+        self
     }
 
-    // Unpacks the package and replaces the old installation with the new installation.
-    #[cfg(not(windows))]
-    #[tracing::instrument(level = "trace", skip(self))]
-    fn unpack(&self, pkg: &Path) -> anyhow::Result<()> {
-        use flate2::read::GzDecoder;
-        use tar::Archive;
+    /// Sets the algorithm of the checksum (defaults to SHA256).
+    pub(crate) fn checksum_algorithm(&mut self, checksum_algorithm: ChecksumAlgorithm) -> &mut Self {
+        self.checksum_algorithm = checksum_algorithm;
 
-        let tmp = self.path.join(METADATA_DIR).join(&self.checksum);
+        self
+    }
 
-        // remove left-overs from last run, if there are any
-        if tmp.exists() {
-            fs::remove_dir_all(&tmp)?;
-        }
+    /// Returns the path of the (cached) package within the metadata directory.
+    pub(crate) fn cached(&self) -> PathBuf {
+        cached(&self.path, &self.checksum, self.archive_format)
+    }
 
-        // check, if current installation is in use
-        let lib = self.path.join("lib");
-        if lib.exists() {
-            let mut lib_renamed = self.path.join("lib");
-            lib_renamed.set_extension(&self.checksum);
+    /// Returns the directory the package is installed into side-by-side, if the versioned layout is used.
+    pub(crate) fn version_dir(&self) -> Option<PathBuf> {
+        self.versioned.as_ref().map(|name| self.path.join(VERSIONS_DIR).join(name))
+    }
 
-            if let Err(err) = fs::rename(&lib, &lib_renamed) {
-                error!(?err, "installation is still in use");
-                println!("installation is still in use");
-                return Err(anyhow::Error::new(err));
-            }
+    /// Checks, if the package can be provided without downloading it (again).
+    pub(crate) fn is_available(&self) -> bool {
+        let companions_cached = self
+            .companions
+            .iter()
+            .all(|companion| cached(&self.path, &companion.checksum, companion.archive_format).exists());
+        (self.cached().exists() && companions_cached) || self.version_dir().is_some_and(|version_dir| version_dir.join("bin").exists())
+    }
 
-            // revert rename
-            let _ = fs::rename(lib_renamed, lib);
+    /// Provide (download and unpack) the package and commit the given metadata afterwards.
+    ///
+    /// Each completed phase is recorded in the journal, so an interrupted update can be recovered by the next run.
+    pub(crate) fn provide(&self, metadata: &Metadata) -> anyhow::Result<()> {
+        // reuse version installed side-by-side (e.g. on rollback)
+        if let Some(version_dir) = self.version_dir()
+            && version_dir.join("bin").exists()
+        {
+            self.switch_current(&version_dir)?;
+            self.journal(metadata, Phase::NewMovedIn)?;
+            return self.commit(metadata);
         }
 
-        // unpack new installation to tmp directory
-        let pkg_file = File::open(pkg)?;
-        let mut archive = Archive::new(GzDecoder::new(pkg_file));
-        for entry in archive.entries()? {
-            let mut entry = entry?;
-
-            // skip entry with dangerous name
-            let Ok(name) = entry.path() else {
-                let path_bytes = &entry.path_bytes();
-                let name = String::from_utf8_lossy(path_bytes);
-                warn!(name = %name, "skipping dangerous name");
-                continue;
-            };
+        let pkg = self.download()?;
+        self.journal(metadata, Phase::Downloaded)?;
+        let tmp = self.unpack(&pkg)?;
+        self.provide_companions(&tmp)?;
+        if let Some(ref verification) = self.verification {
+            verification.verify(&tmp)?;
+        }
+        self.journal(metadata, Phase::Unpacked)?;
+
+        if let Some(version_dir) = self.version_dir() {
+            self.install_versioned(&tmp, &version_dir)?;
+        } else {
+            self.move_old_aside()?;
+            self.journal(metadata, Phase::OldMovedAside)?;
+            self.move_new_in(&tmp)?;
+        }
+        self.journal(metadata, Phase::NewMovedIn)?;
 
-            // skip elements without at least two components
-            let mut components = name.components();
-            if name.components().count() <= 1 {
-                if !entry.header().entry_type().is_dir() {
-                    warn!(name = %name.display(), "skipping unusual name");
-                }
-                continue;
-            }
+        self.commit(metadata)
+    }
 
-            // remove the first component
-            components.next();
-            let name = components.as_path();
-
-            let name = tmp.join(name);
-            trace!("unpacking {name:?}");
-
-            if name.is_dir() {
-                fs::create_dir_all(name)?;
-            } else {
-                if let Some(p) = name.parent() {
-                    if !p.exists() {
-                        fs::create_dir_all(p)?;
-                    }
-                }
-                entry.unpack(name)?;
-            }
+    /// Recovers the interrupted update recorded in the given journal by either completing or reverting it.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub(crate) fn recover(&self, journal: &Journal) -> anyhow::Result<Recovery> {
+        let complete = match journal.phase {
+            Phase::Downloaded => false,
+            // the new version may have been installed side-by-side already
+            Phase::Unpacked => self.version_dir().is_some_and(|version_dir| version_dir.join("bin").exists()),
+            Phase::OldMovedAside | Phase::NewMovedIn | Phase::Committed => true,
+        };
+        if !complete {
+            self.revert(journal.phase)?;
+            return Ok(Recovery::Reverted);
         }
 
-        let java_exe = tmp.join("bin").join("java");
-        if !java_exe.exists() {
-            return Err(anyhow!("failed to verify installation"));
+        let metadata = &journal.metadata;
+        if journal.phase < Phase::NewMovedIn {
+            match self.version_dir() {
+                Some(version_dir) => self.switch_current(&version_dir)?,
+                None => self.move_new_in(&self.tmp())?,
+            }
+            self.journal(metadata, Phase::NewMovedIn)?;
         }
+        self.commit(metadata)?;
+
+        Ok(Recovery::Completed)
+    }
+
+    // Returns the tmp directory the package is unpacked to.
+    fn tmp(&self) -> PathBuf {
+        self.path.join(METADATA_DIR).join(&self.checksum)
+    }
 
-        // TODO further verify installation in tmp by calling java -version ?
+    // Returns the directory the old installation is moved aside to.
+    fn old_dir(&self) -> PathBuf {
+        self.path.join(METADATA_DIR).join(OLD_DIR)
+    }
+
+    // Records the given (completed) phase in the journal.
+    fn journal(&self, metadata: &Metadata, phase: Phase) -> anyhow::Result<()> {
+        trace!(?phase);
+        let journal = Journal {
+            metadata: metadata.clone(),
+            phase,
+        };
+
+        journal.save(Journal::filename(&self.path))
+    }
+
+    // Moves the old installation (everything except the metadata directory) aside.
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn move_old_aside(&self) -> anyhow::Result<()> {
+        // remove left-overs from last run, if there are any
+        let old_dir = self.old_dir();
+        remove_dir_all(&old_dir)?;
+        fs::create_dir_all(&old_dir)?;
 
-        // delete current installation
         let metadata_dir = OsStr::new(METADATA_DIR);
         for entry in fs::read_dir(&self.path)? {
             let entry = entry?;
 
-            let path = entry.path();
-            let Some(name) = path.file_name() else {
+            let from = entry.path();
+            let Some(name) = from.file_name() else {
                 continue;
             };
 
@@ -166,16 +239,16 @@ impl Package {
                 continue;
             }
 
-            // remove
-            if path.is_dir() {
-                fs::remove_dir_all(path)?;
-            } else {
-                fs::remove_file(path)?;
-            }
+            fs::rename(&from, old_dir.join(name))?;
         }
 
-        // move new installation
-        for entry in fs::read_dir(&tmp)? {
+        Ok(())
+    }
+
+    // Moves the new installation from the given tmp directory in (skipping entries moved already).
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn move_new_in(&self, tmp: &Path) -> anyhow::Result<()> {
+        for entry in fs::read_dir(tmp)? {
             let entry = entry?;
 
             let from = entry.path();
@@ -188,139 +261,398 @@ impl Package {
             fs::rename(from, to)?;
         }
 
-        // cleanup tmp directory
-        if let Err(err) = fs::remove_dir_all(tmp) {
+        Ok(())
+    }
+
+    // Reverts an interrupted update by moving the old installation back and removing the tmp directory.
+    //
+    // The old installation is only moved aside after the package has been unpacked, so an old directory found in an
+    // earlier phase is no part of this update and must not be moved over the installation.
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn revert(&self, phase: Phase) -> anyhow::Result<()> {
+        let old_dir = self.old_dir();
+        if phase >= Phase::Unpacked && old_dir.exists() {
+            for entry in fs::read_dir(&old_dir)? {
+                let entry = entry?;
+
+                let from = entry.path();
+                let Some(name) = from.file_name() else {
+                    continue;
+                };
+
+                fs::rename(&from, self.path.join(name))?;
+            }
+            fs::remove_dir(&old_dir)?;
+        }
+
+        remove_dir_all(&self.tmp())?;
+        fs::remove_file(Journal::filename(&self.path))?;
+
+        Ok(())
+    }
+
+    // Writes the metadata of the new installation and removes the old installation, the tmp directory and the journal.
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn commit(&self, metadata: &Metadata) -> anyhow::Result<()> {
+        metadata.save(self.path.join(METADATA_DIR).join(METADATA_FILE))?;
+        self.journal(metadata, Phase::Committed)?;
+
+        // cleanup old installation (a left-over would be moved back by the revert of a later update) and tmp directory
+        remove_dir_all(&self.old_dir()).map_err(|err| anyhow!("failed to delete old installation: {err}"))?;
+        if let Err(err) = remove_dir_all(&self.tmp()) {
             warn!(?err, "failed to delete tmp directory");
         }
+        if let Some(version_dir) = self.version_dir() {
+            self.collect_garbage(&version_dir);
+        }
+
+        fs::remove_file(Journal::filename(&self.path))?;
 
         Ok(())
     }
 
-    // Unpacks the package and replaces the old installation with the new installation.
-    #[expect(clippy::permissions_set_readonly_false)]
-    #[cfg(windows)]
+    // Download the package.
     #[tracing::instrument(level = "trace", skip(self))]
-    fn unpack(&self, pkg: &Path) -> anyhow::Result<()> {
-        let tmp = self.path.join(METADATA_DIR).join(&self.checksum);
+    fn download(&self) -> anyhow::Result<PathBuf> {
+        let dest = self.cached();
+        download(&self.url, &dest, &self.checksum, self.checksum_algorithm)?;
 
-        // remove leftovers from last run, if there are any
-        if tmp.exists() {
-            fs::remove_dir_all(&tmp)?;
+        Ok(dest)
+    }
+
+    // Downloads the companion artefacts and unpacks them into their directories within the given tmp directory.
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn provide_companions(&self, tmp: &Path) -> anyhow::Result<()> {
+        for companion in &self.companions {
+            let dest = cached(&self.path, &companion.checksum, companion.archive_format);
+            let checksum_algorithm = ChecksumAlgorithm::from_checksum(&companion.checksum);
+            download(&companion.url, &dest, &companion.checksum, checksum_algorithm)?;
+            companion.archive_format.unpack(&dest, &tmp.join(&companion.directory))?;
         }
 
-        // check, if current installation is in use
-        let lib = self.path.join("lib");
-        if lib.exists() {
-            let mut lib_renamed = self.path.join("lib");
-            lib_renamed.set_extension(&self.checksum);
+        Ok(())
+    }
 
-            if let Err(err) = fs::rename(&lib, &lib_renamed) {
-                error!(?err, "installation is still in use");
-                println!("installation is still in use");
-                return Err(anyhow::Error::new(err));
-            }
+    // Unpacks the package to the tmp directory and verifies it.
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn unpack(&self, pkg: &Path) -> anyhow::Result<PathBuf> {
+        let tmp = self.tmp();
 
-            // revert rename
-            let _ = fs::rename(lib_renamed, lib);
+        // remove left-overs from last run, if there are any
+        if tmp.exists() {
+            fs::remove_dir_all(&tmp)?;
         }
 
         // unpack new installation to tmp directory
-        let pkg_file = File::open(pkg)?;
-        let mut zip = zip::ZipArchive::new(pkg_file)?;
-        for i in 0..zip.len() {
-            let mut file = zip.by_index(i)?;
-            let Some(name) = file.enclosed_name() else {
-                warn!(name = file.name(), "skipping dangerous name");
-                continue;
-            };
+        self.archive_format.unpack(pkg, &tmp)?;
+        archive::check_java_home(&tmp)?;
 
-            // skip elements without at least two components
-            let mut components = name.components();
-            if name.components().count() <= 1 {
-                if !file.is_dir() {
-                    warn!(name = %name.display(), "skipping unusual name");
-                }
-                continue;
-            }
+        Ok(tmp)
+    }
 
-            // remove the first component
-            components.next();
-            let name = components.as_path();
-
-            let name = tmp.join(name);
-            trace!("unpacking {name:?}");
-
-            if file.is_dir() {
-                fs::create_dir_all(name)?;
-            } else {
-                #[expect(clippy::collapsible_if)]
-                if let Some(p) = name.parent() {
-                    if !p.exists() {
-                        fs::create_dir_all(p)?;
-                    }
-                }
-                let mut outfile = fs::File::create(name)?;
-                std::io::copy(&mut file, &mut outfile)?;
-            }
+    // Moves the new installation into its version directory and switches the `current` symlink to it.
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn install_versioned(&self, tmp: &Path, version_dir: &Path) -> anyhow::Result<()> {
+        if let Some(versions_dir) = version_dir.parent() {
+            fs::create_dir_all(versions_dir)?;
         }
 
-        let java_exe = tmp.join("bin").join("java.exe");
-        if !java_exe.exists() {
-            return Err(anyhow!("failed to verify installation"));
+        // remove left-overs from an incomplete installation, if there are any
+        if version_dir.exists() {
+            fs::remove_dir_all(version_dir)?;
         }
+        fs::rename(tmp, version_dir)?;
 
-        // TODO further verify installation in tmp by calling java -version ?
+        self.switch_current(version_dir)
+    }
 
-        // delete current installation
-        let metadata_dir = OsStr::new(METADATA_DIR);
-        for entry in fs::read_dir(&self.path)? {
-            let entry = entry?;
+    // Switches the `current` symlink atomically to the given version directory.
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn switch_current(&self, version_dir: &Path) -> anyhow::Result<()> {
+        let current = self.path.join(CURRENT_LINK);
+        if current.exists() && !current.is_symlink() {
+            return Err(anyhow!("{} exists, but is no symlink", current.display()));
+        }
 
-            let path = entry.path();
-            let Some(name) = path.file_name() else {
-                continue;
-            };
+        // create new symlink (relative to the installation directory) next to the current one and rename it
+        let Some(name) = version_dir.file_name() else {
+            return Err(anyhow!("invalid version directory {}", version_dir.display()));
+        };
+        let target = Path::new(VERSIONS_DIR).join(name);
+        let mut link = self.path.join(CURRENT_LINK);
+        link.set_extension(&self.checksum);
+        if link.is_symlink() {
+            fs::remove_file(&link)?;
+        }
+        symlink_dir(&target, &link)?;
+        rename_symlink(&link, &current)?;
 
-            // skip metadata directory
-            if name == metadata_dir {
-                continue;
-            }
+        Ok(())
+    }
 
-            // Windows won't delete directories/files marked read-only
-            let metadata = entry.metadata()?;
-            let mut perms = metadata.permissions();
-            if perms.readonly() {
-                perms.set_readonly(false);
-                fs::set_permissions(&path, perms)?;
+    // Removes old versions exceeding the retention count, skipping the current version and versions still in use.
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn collect_garbage(&self, current: &Path) {
+        let versions_dir = self.path.join(VERSIONS_DIR);
+        let Ok(entries) = fs::read_dir(&versions_dir) else {
+            return;
+        };
+
+        // old versions, newest first
+        let mut versions = entries
+            .filter_map(Result::ok)
+            .filter(|entry| entry.path().is_dir() && entry.path() != current)
+            .map(|entry| (entry.metadata().and_then(|m| m.modified()).ok(), entry.path()))
+            .collect::<Vec<_>>();
+        versions.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+        for (_, path) in versions.into_iter().skip(self.retention.saturating_sub(1)) {
+            let processes = inuse::processes_using(&path);
+            if !processes.is_empty() {
+                warn!(version = %path.display(), ?processes, "not removing old version still in use");
+                continue;
             }
 
-            // remove
-            if path.is_dir() {
-                fs::remove_dir_all(path)?;
-            } else {
-                fs::remove_file(path)?;
+            trace!(version = %path.display(), "removing old version");
+            if let Err(err) = remove_version(&path) {
+                warn!(?err, version = %path.display(), "failed to remove old version (still in use?)");
             }
         }
+    }
+}
 
-        // move new installation
-        for entry in fs::read_dir(&tmp)? {
-            let entry = entry?;
+// Returns the path of the (cached) package or companion artefact with the given checksum within the metadata directory.
+fn cached(path: &Path, checksum: &str, archive_format: ArchiveFormat) -> PathBuf {
+    let mut cached = path.join(METADATA_DIR).join(checksum);
+    cached.set_extension(archive_format.ext());
 
-            let from = entry.path();
-            let Some(name) = from.file_name() else {
-                continue;
-            };
+    cached
+}
 
-            let to = self.path.join(name);
+// Downloads the given URL (or copies the local file) to the given destination, unless it has been downloaded already.
+#[tracing::instrument(level = "trace")]
+fn download(url: &str, dest: &Path, checksum: &str, checksum_algorithm: ChecksumAlgorithm) -> anyhow::Result<()> {
+    // check if already downloaded
+    if dest.exists() && checksum::checksum(dest, checksum_algorithm)? == checksum {
+        return Ok(());
+    }
 
-            fs::rename(from, to)?;
+    // download file (or copy local file)
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    trace!(pkg = %dest.display());
+    let dest_file = File::create(dest)?;
+    let mut checksum_write = ChecksumWrite::new(dest_file, checksum_algorithm);
+    let bytes_written = if let Some(src) = local_path(url) {
+        let mut src_file = File::open(src)?;
+        io::copy(&mut src_file, &mut checksum_write)?
+    } else {
+        let client = reqwest::blocking::Client::new();
+        let mut response = client
+            .get(url) //
+            .header(reqwest::header::ACCEPT, "application/octet-stream") //
+            .send()?
+            .error_for_status()?;
+        response.copy_to(&mut checksum_write)?
+    };
+    trace!(bytes_written);
+    let checksum_calculated = checksum_write.checksum()?;
+    trace!(checksum_calculated);
+
+    // calculate/verify checksum
+    if checksum.to_lowercase() != checksum_calculated {
+        return Err(anyhow::Error::msg("hashes differ"));
+    }
+
+    Ok(())
+}
+
+// Removes the given directory, if it exists (Windows won't delete directories/files marked read-only).
+#[cfg_attr(windows, expect(clippy::permissions_set_readonly_false))]
+fn remove_dir_all(path: &Path) -> io::Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+
+    #[cfg(windows)]
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        let mut perms = entry.metadata()?.permissions();
+        if perms.readonly() {
+            perms.set_readonly(false);
+            fs::set_permissions(entry.path(), perms)?;
         }
+    }
 
-        // cleanup tmp directory
-        if let Err(err) = fs::remove_dir_all(tmp) {
-            warn!(?err, "failed to delete tmp directory");
+    fs::remove_dir_all(path)
+}
+
+// Creates a symlink to the given directory.
+#[cfg(not(windows))]
+fn symlink_dir(target: &Path, link: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+// Creates a symlink to the given directory.
+#[cfg(windows)]
+fn symlink_dir(target: &Path, link: &Path) -> io::Result<()> {
+    std::os::windows::fs::symlink_dir(target, link)
+}
+
+// Renames the given symlink, replacing an existing one atomically.
+#[cfg(not(windows))]
+fn rename_symlink(from: &Path, to: &Path) -> io::Result<()> {
+    fs::rename(from, to)
+}
+
+// Renames the given symlink, replacing an existing one (Windows won't replace a directory symlink by renaming).
+#[cfg(windows)]
+fn rename_symlink(from: &Path, to: &Path) -> io::Result<()> {
+    if to.is_symlink() {
+        fs::remove_dir(to)?;
+    }
+    fs::rename(from, to)
+}
+
+// Removes an old version, renaming it first to fail early (on Windows), if it is still in use.
+fn remove_version(path: &Path) -> io::Result<()> {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    if name.ends_with(".delete") {
+        return fs::remove_dir_all(path);
+    }
+
+    let renamed = path.with_file_name(format!("{name}.delete"));
+    fs::rename(path, &renamed)?;
+    fs::remove_dir_all(renamed)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use semver::Version;
+    use test_log::test;
+
+    #[test]
+    fn local_path_of_file_url() {
+        let path = std::env::temp_dir().join("jdk.tar.gz");
+        let url = Url::from_file_path(&path).unwrap();
+        assert_eq!(Some(path), local_path(url.as_str()));
+    }
+
+    #[test]
+    fn local_path_of_http_url() {
+        assert_eq!(None, local_path("https://example.com/jdk.tar.gz"));
+    }
+
+    // Creates a package (tar.gz) containing a fake JDK and returns its URL and checksum.
+    fn fake_package(dir: &Path, release: &str) -> (String, String) {
+        use flate2::Compression;
+        use flate2::write::GzEncoder;
+
+        let pkg = dir.join(format!("jdk-{release}.tar.gz"));
+        let mut builder = tar::Builder::new(GzEncoder::new(File::create(&pkg).unwrap(), Compression::default()));
+        let mut header = tar::Header::new_gnu();
+        header.set_size(release.len() as u64);
+        header.set_mode(0o755);
+        header.set_cksum();
+        builder.append_data(&mut header, format!("jdk-{release}/bin/java"), release.as_bytes()).unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let checksum = checksum::checksum(&pkg, ChecksumAlgorithm::Sha256).unwrap();
+        (Url::from_file_path(&pkg).unwrap().to_string(), checksum)
+    }
+
+    #[test]
+    fn recover_interrupted_update() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("jdk");
+        let java = path.join("bin").join("java");
+        let (url, checksum) = fake_package(tempdir.path(), "21.0.1");
+        let metadata = Metadata::new("direct", Version::new(21, 0, 1), &checksum);
+        Package::new(&path, ArchiveFormat::TarGz, url, &checksum).provide(&metadata).unwrap();
+        assert_eq!("21.0.1", fs::read_to_string(&java).unwrap());
+
+        // interrupted after the old installation has been moved aside → complete
+        let (url, checksum) = fake_package(tempdir.path(), "21.0.2");
+        let metadata = Metadata::new("direct", Version::new(21, 0, 2), &checksum);
+        let package = Package::new(&path, ArchiveFormat::TarGz, url, &checksum);
+        let pkg = package.download().unwrap();
+        package.unpack(&pkg).unwrap();
+        package.move_old_aside().unwrap();
+        package.journal(&metadata, Phase::OldMovedAside).unwrap();
+        let journal = Journal::load(Journal::filename(&path)).unwrap();
+        assert_eq!(Recovery::Completed, package.recover(&journal).unwrap());
+        assert_eq!("21.0.2", fs::read_to_string(&java).unwrap());
+        assert_eq!(metadata, Metadata::load(path.join(METADATA_DIR).join(METADATA_FILE)).unwrap());
+        assert!(!Journal::filename(&path).exists());
+
+        // interrupted while moving the old installation aside → revert
+        let (url, checksum) = fake_package(tempdir.path(), "21.0.3");
+        let metadata = Metadata::new("direct", Version::new(21, 0, 3), &checksum);
+        let package = Package::new(&path, ArchiveFormat::TarGz, url, &checksum);
+        let pkg = package.download().unwrap();
+        package.unpack(&pkg).unwrap();
+        package.journal(&metadata, Phase::Unpacked).unwrap();
+        fs::create_dir_all(package.old_dir()).unwrap();
+        fs::rename(path.join("bin"), package.old_dir().join("bin")).unwrap();
+        let journal = Journal::load(Journal::filename(&path)).unwrap();
+        assert_eq!(Recovery::Reverted, package.recover(&journal).unwrap());
+        assert_eq!("21.0.2", fs::read_to_string(&java).unwrap());
+        assert_eq!(
+            Version::new(21, 0, 2),
+            Metadata::load(path.join(METADATA_DIR).join(METADATA_FILE)).unwrap().version
+        );
+        assert!(!package.tmp().exists());
+        assert!(!Journal::filename(&path).exists());
+
+        // interrupted after the download with a stale old installation → revert (keeping the installation)
+        fs::create_dir_all(package.old_dir().join("bin")).unwrap();
+        fs::write(package.old_dir().join("bin").join("java"), "stale").unwrap();
+        package.download().unwrap();
+        package.journal(&metadata, Phase::Downloaded).unwrap();
+        let journal = Journal::load(Journal::filename(&path)).unwrap();
+        assert_eq!(Recovery::Reverted, package.recover(&journal).unwrap());
+        assert_eq!("21.0.2", fs::read_to_string(&java).unwrap());
+        assert!(!Journal::filename(&path).exists());
+    }
+
+    #[test]
+    fn companions() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("jdk");
+        let (url, checksum) = fake_package(tempdir.path(), "21.0.1");
+        let (companion_url, companion_checksum) = fake_package(tempdir.path(), "21.0.1-debug");
+        let metadata = Metadata::new("direct", Version::new(21, 0, 1), &checksum);
+        let mut package = Package::new(&path, ArchiveFormat::TarGz, url, &checksum);
+        package.companion(Companion::new("debug", ArchiveFormat::TarGz, companion_url, &companion_checksum));
+        package.provide(&metadata).unwrap();
+        assert_eq!("21.0.1", fs::read_to_string(path.join("bin").join("java")).unwrap());
+        assert_eq!("21.0.1-debug", fs::read_to_string(path.join("debug").join("bin").join("java")).unwrap());
+        assert!(package.is_available());
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn versioned_layout() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("jdk");
+        for release in ["21.0.1", "21.0.2", "21.0.3"] {
+            let (url, checksum) = fake_package(tempdir.path(), release);
+            let metadata = Metadata::new("direct", Version::parse(release).unwrap(), &checksum);
+            let mut package = Package::new(&path, ArchiveFormat::TarGz, url, &checksum);
+            package.versioned(format!("{release}-{checksum}"), 2).provide(&metadata).unwrap();
+            assert_eq!(release, fs::read_to_string(path.join(CURRENT_LINK).join("bin").join("java")).unwrap());
+            std::thread::sleep(std::time::Duration::from_millis(10));
         }
 
-        Ok(())
+        // only the current and the previous version are kept
+        let mut versions = fs::read_dir(path.join(VERSIONS_DIR))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().split('-').next().unwrap().to_string())
+            .collect::<Vec<_>>();
+        versions.sort();
+        assert_eq!(vec!["21.0.2", "21.0.3"], versions);
     }
 }
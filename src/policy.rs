@@ -0,0 +1,196 @@
+//! Update policy.
+//!
+//! This module contains the update policy deciding whether an available build gets installed or held back.
+
+use crate::config::PolicyConfig;
+use anyhow::anyhow;
+use semver::Version;
+use std::time::Duration;
+use time::OffsetDateTime;
+
+/// The decision to hold back an available build.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Hold {
+    /// The reason why the build is held back.
+    pub(crate) reason: String,
+    /// The version of the build held back.
+    pub(crate) version: Version,
+}
+
+/// The reaction to an installation in use by running processes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum InUse {
+    /// Proceed with the update anyway.
+    Proceed,
+    /// Skip (hold back) the update.
+    Skip,
+    /// Wait (at most the given timeout) for the processes to terminate and skip the update afterwards.
+    Wait(Duration),
+}
+
+/// Default timeout to wait for an installation to be no longer in use.
+pub(crate) const IN_USE_TIMEOUT_DEFAULT: Duration = Duration::from_secs(10 * 60);
+
+/// Returns the reaction to an installation in use configured by the given policy (defaults to skip).
+pub(crate) fn in_use(policy: &PolicyConfig) -> anyhow::Result<InUse> {
+    match policy.in_use.trim().to_lowercase().as_str() {
+        "" | "skip" => Ok(InUse::Skip),
+        "proceed" => Ok(InUse::Proceed),
+        "wait" => {
+            let timeout = policy.in_use_timeout.trim();
+            if timeout.is_empty() {
+                return Ok(InUse::Wait(IN_USE_TIMEOUT_DEFAULT));
+            }
+            let timeout = humantime::parse_duration(timeout).map_err(|err| anyhow!("invalid in-use-timeout '{timeout}': {err}"))?;
+            Ok(InUse::Wait(timeout))
+        }
+        in_use => Err(anyhow!("invalid in-use '{in_use}' (expected skip, wait or proceed)")),
+    }
+}
+
+/// Evaluates the given policy for the available build and returns the decision to hold it back, if any.
+///
+/// The checks are done in the following order: freeze, security-only (updates only) and min-age.
+pub(crate) fn evaluate(
+    policy: &PolicyConfig,
+    installed: Option<&Version>,
+    available: &Version,
+    release_date: Option<OffsetDateTime>,
+    now: OffsetDateTime,
+) -> anyhow::Result<Option<Hold>> {
+    let hold = |reason: String| {
+        Some(Hold {
+            reason,
+            version: available.clone(),
+        })
+    };
+
+    // freeze
+    if policy.freeze {
+        return Ok(hold("installation is frozen".to_string()));
+    }
+
+    // security-only (the build metadata counts, so out-of-band releases are updates as well)
+    if policy.security_only
+        && let Some(installed) = installed
+        && available > installed
+        && !is_security_release(installed, available)
+    {
+        return Ok(hold(format!("{available} is no security release")));
+    }
+
+    // min-age
+    let min_age = policy.min_age.trim();
+    if !min_age.is_empty() {
+        let min_age = humantime::parse_duration(min_age).map_err(|err| anyhow!("invalid min-age '{min_age}': {err}"))?;
+        let Some(release_date) = release_date else {
+            return Ok(hold("release date unknown".to_string()));
+        };
+        let age = Duration::try_from(now - release_date).unwrap_or_default();
+        if age < min_age {
+            let min_age = humantime::format_duration(min_age);
+            let date = release_date.date();
+            return Ok(hold(format!("released on {date}, younger than {min_age}")));
+        }
+    }
+
+    Ok(None)
+}
+
+// Returns whether the available version is a security (CPU) release compared to the installed version.
+//
+// Security releases increment the update (patch) part, whereas out-of-band releases and re-builds only differ in
+// further parts (kept as build metadata) or in their (early-access) build number.
+#[doc(hidden)]
+fn is_security_release(installed: &Version, available: &Version) -> bool {
+    (available.major, available.minor, available.patch) > (installed.major, installed.minor, installed.patch)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use test_log::test;
+    use time::macros::datetime;
+
+    const NOW: OffsetDateTime = datetime!(2024-10-20 12:00:00 UTC);
+
+    fn version(version: &str) -> Version {
+        Version::parse(version).unwrap()
+    }
+
+    #[test]
+    fn no_policy() {
+        let policy = PolicyConfig::default();
+        let hold = evaluate(&policy, Some(&version("21.0.4")), &version("21.0.5"), None, NOW).unwrap();
+        assert_eq!(None, hold);
+    }
+
+    #[test]
+    fn freeze() {
+        let policy = PolicyConfig {
+            freeze: true,
+            ..Default::default()
+        };
+        let hold = evaluate(&policy, None, &version("21.0.5"), None, NOW).unwrap().unwrap();
+        assert_eq!("installation is frozen", hold.reason);
+        assert_eq!(version("21.0.5"), hold.version);
+    }
+
+    #[test]
+    fn security_only() {
+        let policy = PolicyConfig {
+            security_only: true,
+            ..Default::default()
+        };
+        assert_eq!(None, evaluate(&policy, Some(&version("21.0.4")), &version("21.0.5"), None, NOW).unwrap());
+        assert_eq!(None, evaluate(&policy, None, &version("21.0.5"), None, NOW).unwrap());
+        let hold = evaluate(&policy, Some(&version("11.0.20")), &version("11.0.20+1"), None, NOW).unwrap().unwrap();
+        assert_eq!("11.0.20+1 is no security release", hold.reason); // out-of-band release
+        assert_eq!(None, evaluate(&policy, Some(&version("11.0.20+1")), &version("11.0.20+1"), None, NOW).unwrap());
+        let hold = evaluate(&policy, Some(&version("26.0.0-ea.20")), &version("26.0.0-ea.21"), None, NOW)
+            .unwrap()
+            .unwrap();
+        assert_eq!("26.0.0-ea.21 is no security release", hold.reason);
+    }
+
+    #[test]
+    fn min_age() {
+        let policy = PolicyConfig {
+            min_age: "7d".to_string(),
+            ..Default::default()
+        };
+        let old = datetime!(2024-10-01 00:00:00 UTC);
+        assert_eq!(None, evaluate(&policy, None, &version("21.0.5"), Some(old), NOW).unwrap());
+        let young = datetime!(2024-10-18 00:00:00 UTC);
+        let hold = evaluate(&policy, None, &version("21.0.5"), Some(young), NOW).unwrap().unwrap();
+        assert_eq!("released on 2024-10-18, younger than 7days", hold.reason);
+        let hold = evaluate(&policy, None, &version("21.0.5"), None, NOW).unwrap().unwrap();
+        assert_eq!("release date unknown", hold.reason);
+    }
+
+    #[test]
+    fn min_age_invalid() {
+        let policy = PolicyConfig {
+            min_age: "a week".to_string(),
+            ..Default::default()
+        };
+        assert!(evaluate(&policy, None, &version("21.0.5"), None, NOW).is_err());
+    }
+
+    #[test]
+    fn in_use_policy() {
+        let mut policy = PolicyConfig::default();
+        assert_eq!(InUse::Skip, in_use(&policy).unwrap());
+        policy.in_use = "proceed".to_string();
+        assert_eq!(InUse::Proceed, in_use(&policy).unwrap());
+        policy.in_use = "wait".to_string();
+        assert_eq!(InUse::Wait(IN_USE_TIMEOUT_DEFAULT), in_use(&policy).unwrap());
+        policy.in_use_timeout = "30s".to_string();
+        assert_eq!(InUse::Wait(Duration::from_secs(30)), in_use(&policy).unwrap());
+        policy.in_use_timeout = "soon".to_string();
+        assert!(in_use(&policy).is_err());
+        policy.in_use = "ignore".to_string();
+        assert!(in_use(&policy).is_err());
+    }
+}
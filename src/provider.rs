@@ -0,0 +1,677 @@
+//! Vendor provider.
+//!
+//! This module contains the abstraction each vendor implements to take part in the installation pipeline.
+
+#[cfg(any(
+    feature = "azul",
+    feature = "corretto",
+    feature = "disco",
+    feature = "eclipse",
+    feature = "liberica",
+    feature = "manifest",
+    feature = "microsoft",
+    feature = "sapmachine",
+    feature = "semeru"
+))]
+use crate::arch::Arch;
+use crate::archive::ArchiveFormat;
+use crate::checksum::ChecksumAlgorithm;
+use crate::vars::host_libc;
+use crate::vendor::*;
+use anyhow::anyhow;
+use semver::{BuildMetadata, Prerelease, Version, VersionReq};
+use std::env;
+use std::fmt;
+#[cfg(any(
+    feature = "eclipse",
+    feature = "github-release",
+    feature = "manifest",
+    feature = "sapmachine",
+    feature = "semeru"
+))]
+use time::Date;
+use time::OffsetDateTime;
+#[cfg(any(
+    feature = "eclipse",
+    feature = "github-release",
+    feature = "manifest",
+    feature = "sapmachine",
+    feature = "semeru"
+))]
+use time::format_description::well_known::Rfc3339;
+#[cfg(any(
+    feature = "eclipse",
+    feature = "github-release",
+    feature = "manifest",
+    feature = "sapmachine",
+    feature = "semeru"
+))]
+use time::macros::format_description;
+
+/// Trait for vendor providers.
+///
+/// A provider only knows how to query its vendor for the package that fulfills a [`MetadataRequest`].
+/// Everything else (loading/saving metadata, downloading, unpacking, notifying) is done by the
+/// [`Installation`](crate::installation::Installation).
+pub(crate) trait VendorProvider: fmt::Debug + Sync {
+    /// Returns the vendor of this provider.
+    fn vendor(&self) -> Vendor;
+
+    /// Queries the vendor for the package that fulfills the given request.
+    fn query(&self, request: &MetadataRequest) -> anyhow::Result<MetadataResponse>;
+}
+
+// The registry with all supported providers.
+#[doc(hidden)]
+static PROVIDERS: &[&dyn VendorProvider] = &[
+    #[cfg(feature = "azul")]
+    &crate::azul::AzulProvider,
+    #[cfg(feature = "corretto")]
+    &crate::corretto::CorrettoProvider,
+    #[cfg(feature = "eclipse")]
+    &crate::eclipse::EclipseProvider,
+    #[cfg(feature = "liberica")]
+    &crate::liberica::LibericaProvider,
+    #[cfg(feature = "microsoft")]
+    &crate::microsoft::MicrosoftProvider,
+    #[cfg(feature = "sapmachine")]
+    &crate::sapmachine::SapMachineProvider,
+    #[cfg(feature = "semeru")]
+    &crate::semeru::SemeruProvider,
+    #[cfg(feature = "disco")]
+    &crate::disco::DiscoProvider,
+    #[cfg(feature = "manifest")]
+    &crate::manifest::ManifestProvider,
+    #[cfg(feature = "direct")]
+    &crate::direct::DirectProvider,
+    #[cfg(feature = "github-release")]
+    &crate::github_release::GithubReleaseProvider,
+];
+
+/// Returns the registered provider for the given vendor.
+pub(crate) fn provider(vendor: Vendor) -> Option<&'static dyn VendorProvider> {
+    PROVIDERS.iter().copied().find(|provider| provider.vendor() == vendor)
+}
+
+/// Parses a java version (e.g. 21, 21.0.1, 11.0.20.1, 21.0.1+12 or 24-ea+20) into a semantic version.
+///
+/// Missing minor and patch parts default to zero, any additional parts end up in the build metadata.
+/// Pre-release versions carry the build number in the pre-release part (e.g. 24.0.0-ea.20), so that
+/// builds of the same pre-release compare correctly.
+pub(crate) fn parse_version(version: &str) -> anyhow::Result<Version> {
+    let (version_part, build_part) = version.trim().split_once('+').unwrap_or((version.trim(), ""));
+    let (version_part, pre_part) = version_part.split_once('-').unwrap_or((version_part, ""));
+    let mut parts = version_part.split('.');
+    let mut number = |name: &str, required: bool| -> anyhow::Result<u64> {
+        match parts.next() {
+            Some(part) => part.parse().map_err(|_| anyhow!("{name} part of '{version}' is not a number")),
+            None if required => Err(anyhow!("{name} part not present in '{version}'")),
+            None => Ok(0),
+        }
+    };
+    let major = number("major", true)?;
+    let minor = number("minor", false)?;
+    let patch = number("patch", false)?;
+
+    let mut version = Version::new(major, minor, patch);
+    let build: Vec<&str> = parts.chain(build_part.split('.')).filter(|part| !part.is_empty()).collect();
+    if !build.is_empty() {
+        version.build = BuildMetadata::new(&build.join("."))?;
+    }
+    if !pre_part.is_empty() {
+        version = prerelease(version, pre_part)?;
+    }
+
+    Ok(version)
+}
+
+/// Marks the given version as early-access build (e.g. 24.0.0+20 becomes 24.0.0-ea.20).
+#[cfg(any(
+    feature = "azul",
+    feature = "eclipse",
+    feature = "github-release",
+    feature = "liberica",
+    feature = "sapmachine",
+    feature = "semeru"
+))]
+pub(crate) fn early_access(version: Version) -> Version {
+    if !version.pre.is_empty() {
+        return version;
+    }
+
+    prerelease(version.clone(), "ea").unwrap_or(version)
+}
+
+// Marks the given version as pre-release with the given identifier and moves the build number into the pre-release part, if possible.
+#[doc(hidden)]
+fn prerelease(mut version: Version, pre: &str) -> anyhow::Result<Version> {
+    let build = version.build.as_str();
+    if !build.is_empty()
+        && let Ok(pre) = Prerelease::new(&format!("{pre}.{build}"))
+    {
+        version.pre = pre;
+        version.build = BuildMetadata::EMPTY;
+    } else {
+        version.pre = Prerelease::new(pre)?;
+    }
+
+    Ok(version)
+}
+
+/// Parses a release date (e.g. 2024-10-15T12:34:56Z or 2024-10-15) as published by the vendor APIs.
+#[cfg(any(
+    feature = "eclipse",
+    feature = "github-release",
+    feature = "manifest",
+    feature = "sapmachine",
+    feature = "semeru"
+))]
+pub(crate) fn parse_release_date(value: &str) -> Option<OffsetDateTime> {
+    let value = value.trim();
+    if let Ok(date) = OffsetDateTime::parse(value, &Rfc3339) {
+        return Some(date);
+    }
+
+    let date = Date::parse(value, format_description!("[year]-[month]-[day]")).ok()?;
+    Some(date.midnight().assume_utc())
+}
+
+/// Parses a version requirement (e.g. 21, 17.0.x, 17.0.9 or >=21.0.2, <21.0.5) into a semantic version requirement.
+///
+/// A major version only (e.g. 21) requires any version of that major, a full version (e.g. 17.0.9) pins exactly
+/// that version, everything else is parsed as a semantic version requirement. Versions with more than three parts
+/// (e.g. 11.0.20.1) are rejected, as the additional parts can't be told apart from the build number.
+pub(crate) fn parse_version_req(version: &str) -> anyhow::Result<VersionReq> {
+    let version = version.trim();
+    if !version.is_empty() && version.chars().all(|c| c.is_ascii_digit()) {
+        return Ok(VersionReq::parse(&format!("^{version}"))?);
+    }
+    if version.contains('.') && version.chars().all(|c| c.is_ascii_digit() || c == '.') {
+        if version.split('.').count() > 3 {
+            return Err(anyhow!("invalid version requirement '{version}': only major, minor and patch can be pinned"));
+        }
+        let version = parse_version(version)?;
+        let (major, minor, patch) = (version.major, version.minor, version.patch);
+        return Ok(VersionReq::parse(&format!("={major}.{minor}.{patch}"))?);
+    }
+
+    VersionReq::parse(version).map_err(|err| anyhow!("invalid version requirement '{version}': {err}"))
+}
+
+/// The supported package types (variants).
+pub(crate) const PACKAGE_TYPES: &[&str] = &["jdk", "jre", "jdk+fx", "jre+fx"];
+
+/// The request to retrieve the metadata.
+#[derive(Debug, Default)]
+pub(crate) struct MetadataRequest {
+    #[cfg(feature = "github-release")]
+    pub(crate) api_url: String,
+    #[cfg(any(
+        feature = "azul",
+        feature = "corretto",
+        feature = "disco",
+        feature = "eclipse",
+        feature = "liberica",
+        feature = "manifest",
+        feature = "microsoft",
+        feature = "sapmachine",
+        feature = "semeru"
+    ))]
+    pub(crate) arch: String,
+    #[cfg(feature = "github-release")]
+    pub(crate) asset: String,
+    #[cfg(feature = "liberica")]
+    pub(crate) bundle: String,
+    #[cfg(any(
+        feature = "azul",
+        feature = "corretto",
+        feature = "disco",
+        feature = "eclipse",
+        feature = "github-release",
+        feature = "liberica",
+        feature = "manifest",
+        feature = "microsoft",
+        feature = "sapmachine",
+        feature = "semeru"
+    ))]
+    pub(crate) channel: String,
+    #[cfg(feature = "github-release")]
+    pub(crate) checksum_asset: String,
+    pub(crate) companions: Vec<String>,
+    #[cfg(feature = "disco")]
+    pub(crate) distribution: String,
+    #[cfg(feature = "direct")]
+    pub(crate) file: String,
+    #[cfg(any(feature = "eclipse", feature = "semeru"))]
+    pub(crate) jvm_impl: String,
+    pub(crate) libc: String,
+    #[cfg(feature = "manifest")]
+    pub(crate) manifest: String,
+    pub(crate) os: String,
+    pub(crate) package_type: String,
+    #[cfg(feature = "direct")]
+    pub(crate) release: String,
+    #[cfg(feature = "github-release")]
+    pub(crate) repository: String,
+    #[cfg(feature = "direct")]
+    pub(crate) sha256: String,
+    #[cfg(feature = "direct")]
+    pub(crate) url: String,
+    pub(crate) version: String,
+}
+
+impl MetadataRequest {
+    /// Returns the requested architecture for the package (defaults to the one of the host).
+    #[cfg(any(
+        feature = "azul",
+        feature = "corretto",
+        feature = "disco",
+        feature = "eclipse",
+        feature = "liberica",
+        feature = "manifest",
+        feature = "microsoft",
+        feature = "sapmachine",
+        feature = "semeru"
+    ))]
+    pub(crate) fn arch(&self) -> anyhow::Result<Arch> {
+        let arch = self.arch.trim();
+        if arch.is_empty() {
+            return Arch::host().ok_or_else(|| anyhow!("unsupported architecture '{}'", env::consts::ARCH));
+        }
+
+        Arch::try_from(arch).map_err(|err| anyhow!("{err} '{arch}'"))
+    }
+
+    /// Returns the requested architecture for the package, named as used by the given vendor.
+    #[cfg(any(
+        feature = "azul",
+        feature = "corretto",
+        feature = "disco",
+        feature = "eclipse",
+        feature = "liberica",
+        feature = "microsoft",
+        feature = "sapmachine",
+        feature = "semeru"
+    ))]
+    pub(crate) fn arch_name(&self, vendor: Vendor) -> anyhow::Result<&'static str> {
+        let arch = self.arch()?;
+        arch.name(vendor).ok_or_else(|| anyhow!("architecture {arch} is not supported by {vendor}"))
+    }
+
+    /// Returns the archive format of the package for the requested operating system.
+    pub(crate) fn archive_format(&self) -> ArchiveFormat {
+        ArchiveFormat::for_os(&self.os())
+    }
+
+    /// Returns the requested bundle (standard, full or lite) for the package.
+    #[cfg(feature = "liberica")]
+    pub(crate) fn bundle(&self) -> String {
+        let bundle = self.bundle.trim().to_lowercase();
+        match bundle.as_str() {
+            "full" | "lite" => bundle,
+            _ => "standard".to_string(), // default to standard
+        }
+    }
+
+    /// Returns the requested channel (ga or ea) for the package.
+    #[cfg(any(
+        feature = "azul",
+        feature = "corretto",
+        feature = "disco",
+        feature = "eclipse",
+        feature = "github-release",
+        feature = "liberica",
+        feature = "manifest",
+        feature = "microsoft",
+        feature = "sapmachine",
+        feature = "semeru"
+    ))]
+    pub(crate) fn channel(&self) -> String {
+        let channel = self.channel.trim().to_lowercase();
+        match channel.as_str() {
+            "ea" | "early-access" => "ea".to_string(),
+            _ => "ga".to_string(), // default to GA
+        }
+    }
+
+    /// Returns whether early-access builds are requested.
+    #[cfg(any(
+        feature = "azul",
+        feature = "corretto",
+        feature = "eclipse",
+        feature = "github-release",
+        feature = "liberica",
+        feature = "manifest",
+        feature = "microsoft",
+        feature = "sapmachine",
+        feature = "semeru"
+    ))]
+    pub(crate) fn is_early_access(&self) -> bool {
+        self.channel() == "ea"
+    }
+
+    /// Returns the requested JVM implementation (hotspot or openj9) for the package.
+    #[cfg(any(feature = "eclipse", feature = "semeru"))]
+    pub(crate) fn jvm_impl(&self) -> String {
+        let jvm_impl = self.jvm_impl.trim().to_lowercase();
+        match jvm_impl.as_str() {
+            "openj9" => jvm_impl,
+            _ => "hotspot".to_string(), // default to HotSpot
+        }
+    }
+
+    /// Returns the requested C library (glibc or musl) for the package, if the requested operating system is Linux.
+    pub(crate) fn libc(&self) -> Option<String> {
+        if self.os() != "linux" {
+            return None;
+        }
+
+        let libc = self.libc.trim().to_lowercase();
+        let libc = if libc.is_empty() { host_libc().to_string() } else { libc };
+        match libc.as_str() {
+            "musl" => Some(libc),
+            _ => Some("glibc".to_string()), // default to glibc
+        }
+    }
+
+    /// Returns the requested operating system for the package.
+    pub(crate) fn os(&self) -> String {
+        let os = self.os.trim();
+        if os.is_empty() { env::consts::OS.to_lowercase() } else { os.to_lowercase() }
+    }
+
+    /// Returns the requested type (jdk or jre) for the package, without the JavaFX modifier.
+    pub(crate) fn package_type(&self) -> String {
+        let package_type = self.package_type.trim();
+        if package_type.is_empty() {
+            return "jdk".to_string(); // default to JDK
+        }
+
+        let package_type = package_type.to_lowercase();
+        match package_type.trim_end_matches("+fx") {
+            "jre" => "jre".to_string(),
+            _ => "jdk".to_string(), // default to JDK
+        }
+    }
+
+    /// Returns the requested variant (jdk, jre, jdk+fx or jre+fx) for the package.
+    pub(crate) fn package_variant(&self) -> String {
+        let package_type = self.package_type();
+        if self.is_javafx() { format!("{package_type}+fx") } else { package_type }
+    }
+
+    /// Returns whether a package bundled with JavaFX is requested.
+    pub(crate) fn is_javafx(&self) -> bool {
+        self.package_type.trim().to_lowercase().ends_with("+fx")
+    }
+
+    /// Returns the requested (major) version for the package.
+    ///
+    /// For version requirements, the major version is taken from the first comparator (e.g. 21 for >=21.0.2, <22).
+    pub(crate) fn version(&self) -> String {
+        let version = self.version.trim();
+        if version.is_empty() {
+            return "17".to_string(); // default to 17
+        }
+
+        match parse_version_req(version) {
+            Ok(req) => req
+                .comparators
+                .first()
+                .map_or_else(|| version.to_lowercase(), |comparator| comparator.major.to_string()),
+            Err(_) => version.to_lowercase(),
+        }
+    }
+
+    /// Returns the requested version requirement for the package.
+    pub(crate) fn version_req(&self) -> anyhow::Result<VersionReq> {
+        let version = self.version.trim();
+        if version.is_empty() {
+            parse_version_req(&self.version())
+        } else {
+            parse_version_req(version)
+        }
+    }
+
+    /// Returns whether the latest version of the requested major version is requested (no version requirement).
+    #[cfg(any(feature = "azul", feature = "disco", feature = "eclipse", feature = "liberica"))]
+    pub(crate) fn is_latest(&self) -> bool {
+        let version = self.version.trim();
+        version.is_empty() || version.chars().all(|c| c.is_ascii_digit())
+    }
+
+    /// Returns whether the given version fulfills the requested version requirement.
+    ///
+    /// Pre-release and build parts of the given version are ignored, so that early-access builds match as well.
+    pub(crate) fn matches(&self, version: &Version) -> bool {
+        let version = Version::new(version.major, version.minor, version.patch);
+        self.version_req().is_ok_and(|req| req.matches(&version))
+    }
+}
+
+/// The response to the [`MetadataRequest`].
+#[derive(Debug)]
+pub(crate) struct MetadataResponse {
+    pub(crate) checksum: String,
+    pub(crate) checksum_algorithm: ChecksumAlgorithm,
+    pub(crate) companions: Vec<MetadataCompanion>,
+    pub(crate) release_date: Option<OffsetDateTime>,
+    pub(crate) url: String,
+    pub(crate) version: Version,
+}
+
+/// A companion artefact (e.g. debug image) of the package within the [`MetadataResponse`].
+#[derive(Debug)]
+pub(crate) struct MetadataCompanion {
+    pub(crate) checksum: String,
+    pub(crate) kind: String,
+    pub(crate) url: String,
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn defaults() {
+        let request = MetadataRequest::default();
+        #[cfg(any(
+            feature = "azul",
+            feature = "corretto",
+            feature = "disco",
+            feature = "eclipse",
+            feature = "liberica",
+            feature = "manifest",
+            feature = "microsoft",
+            feature = "sapmachine",
+            feature = "semeru"
+        ))]
+        assert_eq!(Arch::host(), request.arch().ok());
+        assert_eq!(env::consts::OS, request.os());
+        #[cfg(any(
+            feature = "azul",
+            feature = "corretto",
+            feature = "disco",
+            feature = "eclipse",
+            feature = "github-release",
+            feature = "liberica",
+            feature = "manifest",
+            feature = "microsoft",
+            feature = "sapmachine",
+            feature = "semeru"
+        ))]
+        assert_eq!("ga", request.channel());
+        #[cfg(any(feature = "eclipse", feature = "semeru"))]
+        assert_eq!("hotspot", request.jvm_impl());
+        assert_eq!("jdk", request.package_type());
+        assert_eq!("17", request.version());
+    }
+
+    #[test]
+    fn libc() {
+        let request = |os: &str, libc: &str| MetadataRequest {
+            libc: libc.to_string(),
+            os: os.to_string(),
+            ..Default::default()
+        };
+        assert_eq!(Some("musl".to_string()), request("linux", "MUSL").libc());
+        assert_eq!(Some("glibc".to_string()), request("linux", "glibc").libc());
+        assert_eq!(Some("glibc".to_string()), request("linux", "gnu").libc());
+        assert_eq!(None, request("windows", "musl").libc());
+    }
+
+    #[cfg(any(
+        feature = "azul",
+        feature = "corretto",
+        feature = "eclipse",
+        feature = "github-release",
+        feature = "liberica",
+        feature = "manifest",
+        feature = "microsoft",
+        feature = "sapmachine",
+        feature = "semeru"
+    ))]
+    #[test]
+    fn early_access_channel() {
+        let request = MetadataRequest {
+            channel: "EA".to_string(),
+            ..Default::default()
+        };
+        assert!(request.is_early_access());
+    }
+
+    #[test]
+    fn unknown_package_type() {
+        let request = MetadataRequest {
+            package_type: "JRX".to_string(),
+            ..Default::default()
+        };
+        assert_eq!("jdk", request.package_type());
+    }
+
+    #[test]
+    fn javafx_package_type() {
+        let request = MetadataRequest {
+            package_type: "JRE+FX".to_string(),
+            ..Default::default()
+        };
+        assert!(request.is_javafx());
+        assert_eq!("jre", request.package_type());
+        assert_eq!("jre+fx", request.package_variant());
+
+        let request = MetadataRequest::default();
+        assert!(!request.is_javafx());
+        assert_eq!("jdk", request.package_variant());
+    }
+
+    #[test]
+    fn parse_version_major_only() {
+        assert_eq!(Version::new(21, 0, 0), parse_version("21").unwrap());
+    }
+
+    #[test]
+    fn parse_version_with_build() {
+        assert_eq!(Version::parse("21.0.1+12").unwrap(), parse_version("21.0.1+12").unwrap());
+        assert_eq!(Version::parse("11.0.20+1").unwrap(), parse_version("11.0.20.1").unwrap());
+    }
+
+    #[test]
+    fn parse_version_early_access() {
+        assert_eq!(Version::parse("24.0.0-ea.20").unwrap(), parse_version("24-ea+20").unwrap());
+        assert_eq!(Version::parse("24.0.0-ea").unwrap(), parse_version("24-ea").unwrap());
+        assert!(parse_version("24-ea+20").unwrap() < parse_version("24-ea+100").unwrap());
+        assert!(parse_version("24-ea+100").unwrap() < parse_version("24").unwrap());
+    }
+
+    #[cfg(any(
+        feature = "azul",
+        feature = "eclipse",
+        feature = "github-release",
+        feature = "liberica",
+        feature = "sapmachine",
+        feature = "semeru"
+    ))]
+    #[test]
+    fn early_access_version() {
+        assert_eq!(Version::parse("24.0.0-ea.20").unwrap(), early_access(Version::parse("24.0.0+20").unwrap()));
+        assert_eq!(Version::parse("24.0.0-ea").unwrap(), early_access(Version::new(24, 0, 0)));
+        assert_eq!(Version::parse("24.0.0-ea.1").unwrap(), early_access(Version::parse("24.0.0-ea.1").unwrap()));
+    }
+
+    #[cfg(any(
+        feature = "eclipse",
+        feature = "github-release",
+        feature = "manifest",
+        feature = "sapmachine",
+        feature = "semeru"
+    ))]
+    #[test]
+    fn release_dates() {
+        let expected = time::macros::datetime!(2024-10-15 12:34:56 UTC);
+        assert_eq!(Some(expected), parse_release_date("2024-10-15T12:34:56Z"));
+        let expected = time::macros::datetime!(2024-10-15 00:00:00 UTC);
+        assert_eq!(Some(expected), parse_release_date("2024-10-15"));
+        assert_eq!(None, parse_release_date("yesterday"));
+    }
+
+    #[test]
+    fn version_requirements() {
+        let request = |version: &str| MetadataRequest {
+            version: version.to_string(),
+            ..Default::default()
+        };
+        let version = |version: &str| Version::parse(version).unwrap();
+
+        let major = request("21");
+        #[cfg(any(feature = "azul", feature = "disco", feature = "eclipse", feature = "liberica"))]
+        assert!(major.is_latest());
+        assert_eq!("21", major.version());
+        assert!(major.matches(&version("21.0.5+11")));
+        assert!(major.matches(&version("21.0.0-ea.20")));
+        assert!(!major.matches(&version("22.0.1")));
+
+        let wildcard = request("17.0.x");
+        #[cfg(any(feature = "azul", feature = "disco", feature = "eclipse", feature = "liberica"))]
+        assert!(!wildcard.is_latest());
+        assert_eq!("17", wildcard.version());
+        assert!(wildcard.matches(&version("17.0.12")));
+        assert!(!wildcard.matches(&version("17.1.0")));
+
+        let range = request(">=21.0.2, <21.0.5");
+        assert_eq!("21", range.version());
+        assert!(range.matches(&version("21.0.4+7")));
+        assert!(!range.matches(&version("21.0.5")));
+        assert!(!range.matches(&version("21.0.1")));
+
+        let exact = request("17.0.9");
+        assert_eq!("17", exact.version());
+        assert!(exact.matches(&version("17.0.9+9")));
+        assert!(!exact.matches(&version("17.0.10")));
+
+        assert!(request("17.0.y").version_req().is_err());
+        assert!(request("11.0.20.1").version_req().is_err());
+        assert!(request("").matches(&version("17.0.1")));
+    }
+
+    #[test]
+    fn parse_version_invalid() {
+        assert!(parse_version("").is_err());
+        assert!(parse_version("jdk-21").is_err());
+    }
+
+    #[test]
+    fn registry() {
+        for registered in PROVIDERS {
+            let vendor = registered.vendor();
+            assert_eq!(Ok(vendor), Vendor::try_from(vendor.id()));
+            assert_eq!(vendor, provider(vendor).unwrap().vendor());
+            assert_eq!(
+                1,
+                PROVIDERS.iter().filter(|provider| provider.vendor() == vendor).count(),
+                "{vendor} registered twice"
+            );
+        }
+    }
+}
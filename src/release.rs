@@ -0,0 +1,141 @@
+//! Release file.
+//!
+//! This module contains the `release` file found in the root directory of every JDK (or JRE).
+
+use crate::provider::parse_version;
+use anyhow::{Result, anyhow};
+use semver::{BuildMetadata, Version};
+use std::fs;
+use std::path::Path;
+
+/// Name of the release file within the root directory of a JDK (or JRE).
+pub(crate) const RELEASE_FILE: &str = "release";
+
+/// Struct to hold the relevant entries of the release file.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Release {
+    /// The implementor of the build (e.g. Eclipse Adoptium)
+    pub(crate) implementor: String,
+    /// The full version of the build (e.g. 21.0.5+11-LTS)
+    pub(crate) java_runtime_version: String,
+    /// The version of the build (e.g. 21.0.5)
+    pub(crate) java_version: String,
+}
+
+impl Release {
+    /// Loads the `Release` from the given filename.
+    #[tracing::instrument(err(level = "trace"), level = "trace")]
+    pub(crate) fn load<P>(filename: P) -> Result<Self>
+    where
+        P: AsRef<Path> + std::fmt::Debug,
+    {
+        let content = fs::read_to_string(filename)?;
+
+        Ok(Self::parse(&content))
+    }
+
+    /// Parses the `Release` from the given content (lines like `KEY="value"`).
+    pub(crate) fn parse(content: &str) -> Self {
+        let mut release = Self::default();
+        for line in content.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = value.trim().trim_matches('"').to_string();
+            match key.trim() {
+                "IMPLEMENTOR" => release.implementor = value,
+                "JAVA_RUNTIME_VERSION" => release.java_runtime_version = value,
+                "JAVA_VERSION" => release.java_version = value,
+                _ => {}
+            }
+        }
+
+        release
+    }
+
+    /// Returns the version of the build (e.g. 21.0.5 for 21.0.5+11-LTS, 8.0.432 for 1.8.0_432-b06 or 24.0.0-ea.20 for
+    /// 24-ea+20-2345).
+    pub(crate) fn version(&self) -> Result<Version> {
+        let version = match self.java_runtime_version.as_str() {
+            "" => self.java_version.as_str(),
+            version => version,
+        };
+        if version.is_empty() {
+            return Err(anyhow!("neither JAVA_RUNTIME_VERSION nor JAVA_VERSION present in release file"));
+        }
+
+        // legacy versions (e.g. 1.8.0_432-b06)
+        if let Some(legacy) = version.strip_prefix("1.") {
+            let (version, update) = legacy.split_once('_').unwrap_or((legacy, "0"));
+            let update: String = update.chars().take_while(char::is_ascii_digit).collect();
+            let version = parse_version(version)?;
+            return Ok(Version::new(version.major, version.minor, update.parse().unwrap_or_default()));
+        }
+
+        // only keep the build number (e.g. 20 of 24-ea+20-2345)
+        let (version, build) = version.split_once('+').unwrap_or((version, ""));
+        let build: String = build.chars().take_while(char::is_ascii_digit).collect();
+        let mut version = match build.as_str() {
+            "" => parse_version(version)?,
+            build => parse_version(&format!("{version}+{build}"))?,
+        };
+
+        // the build number only matters for pre-releases (already moved into the pre-release part)
+        version.build = BuildMetadata::EMPTY;
+
+        Ok(version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn parse() {
+        let content = r#"
+IMPLEMENTOR="Eclipse Adoptium"
+IMPLEMENTOR_VERSION="Temurin-21.0.5+11"
+JAVA_RUNTIME_VERSION="21.0.5+11-LTS"
+JAVA_VERSION="21.0.5"
+JAVA_VERSION_DATE="2024-10-15"
+"#;
+        let release = Release::parse(content);
+        assert_eq!("Eclipse Adoptium", release.implementor);
+        assert_eq!("21.0.5+11-LTS", release.java_runtime_version);
+        assert_eq!("21.0.5", release.java_version);
+        assert_eq!(Version::new(21, 0, 5), release.version().unwrap());
+    }
+
+    #[test]
+    fn legacy_version() {
+        let release = Release::parse("JAVA_RUNTIME_VERSION=\"1.8.0_432-b06\"\nJAVA_VERSION=\"1.8.0_432\"");
+        assert_eq!(Version::new(8, 0, 432), release.version().unwrap());
+        let release = Release::parse("JAVA_VERSION=\"1.8.0_432\"");
+        assert_eq!(Version::new(8, 0, 432), release.version().unwrap());
+    }
+
+    #[test]
+    fn early_access_version() {
+        let release = Release::parse("JAVA_RUNTIME_VERSION=\"24-ea+20-2345\"\nJAVA_VERSION=\"24\"");
+        assert_eq!(Version::parse("24.0.0-ea.20").unwrap(), release.version().unwrap());
+    }
+
+    #[cfg(all(feature = "azul", feature = "eclipse"))]
+    #[test]
+    fn implementor_vendor() {
+        use crate::vendor::Vendor;
+
+        assert_eq!(Some(Vendor::Azul), Vendor::from_implementor("Azul Systems, Inc."));
+        assert_eq!(Some(Vendor::Eclipse), Vendor::from_implementor("Eclipse Adoptium"));
+        assert_eq!(None, Vendor::from_implementor("Oracle Corporation"));
+    }
+
+    #[test]
+    fn missing_version() {
+        let release = Release::parse("IMPLEMENTOR=\"Azul Systems, Inc.\"");
+        assert!(release.version().is_err());
+    }
+}
@@ -0,0 +1,106 @@
+use super::*;
+use crate::checksum::ChecksumAlgorithm;
+use crate::github;
+use anyhow::anyhow;
+use reqwest::blocking::Client;
+use semver::Version;
+
+// Prefix of all release tags.
+#[doc(hidden)]
+const TAG_PREFIX: &str = "sapmachine-";
+
+// Query the API for all relevant data.
+pub(super) fn query(request: &MetadataRequest) -> anyhow::Result<MetadataResponse> {
+    let client = Client::new();
+    let (release, version) = github::latest_release(&client, REPOSITORY, request, |tag| {
+        tag.strip_prefix(TAG_PREFIX).and_then(|version| parse_version(version).ok())
+    })?;
+
+    response_from_release(&client, request, &release, version)
+}
+
+// Build the response out of the matching asset (and its checksum asset) of the given release.
+fn response_from_release(client: &Client, request: &MetadataRequest, release: &serde_json::Value, version: Version) -> anyhow::Result<MetadataResponse> {
+    let Some(tag) = release["tag_name"].as_str() else {
+        return Err(anyhow!("field 'tag_name' not present in response"));
+    };
+
+    // url
+
+    let basename = basename(request, tag)?;
+    let name = format!("{basename}.{}", request.archive_format());
+    let Some(asset) = github::find_asset(release, |n| n == name) else {
+        return Err(anyhow!("no package {name} found in release {tag}"));
+    };
+    let Some(url) = asset["browser_download_url"].as_str() else {
+        return Err(anyhow!("field 'browser_download_url' not present in response"));
+    };
+
+    // checksum
+
+    let checksum_name = format!("{basename}.sha256.txt");
+    let checksum_asset = github::find_asset(release, |n| n == checksum_name);
+    let checksum = github::checksum(client, asset, checksum_asset)?;
+
+    Ok(MetadataResponse {
+        checksum,
+        checksum_algorithm: ChecksumAlgorithm::Sha256,
+        companions: Vec::new(),
+        release_date: release["published_at"].as_str().and_then(parse_release_date),
+        url: url.to_string(),
+        version,
+    })
+}
+
+// Returns the name of the assets (without extension) for the package within the release with the given tag.
+fn basename(request: &MetadataRequest, tag: &str) -> anyhow::Result<String> {
+    let version = tag.strip_prefix(TAG_PREFIX).unwrap_or(tag);
+    let package_type = request.package_type();
+    let os = os(request);
+    let arch = arch(request)?;
+    Ok(format!("{TAG_PREFIX}{package_type}-{version}_{os}-{arch}_bin"))
+}
+
+// Returns the requested architecture for the package, named as used by the assets.
+fn arch(request: &MetadataRequest) -> anyhow::Result<&'static str> {
+    request.arch_name(Vendor::SapMachine)
+}
+
+// Returns the requested operating system for the package, normalized for the assets.
+fn os(request: &MetadataRequest) -> String {
+    match request.os().as_str() {
+        "darwin" | "mac" | "macosx" => "macos".to_string(),
+        os => os.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn test_basename() {
+        let request = MetadataRequest {
+            arch: "x86_64".to_string(),
+            os: "linux".to_string(),
+            package_type: "jre".to_string(),
+            version: "21".to_string(),
+            ..Default::default()
+        };
+        assert_eq!("sapmachine-jre-21.0.1_linux-x64_bin", basename(&request, "sapmachine-21.0.1").unwrap());
+    }
+
+    #[test]
+    fn test_normalize_arm64_architecture() {
+        let request = MetadataRequest {
+            arch: "arm64".to_string(),
+            os: "macos".to_string(),
+            package_type: "jdk".to_string(),
+            version: "21".to_string(),
+            ..Default::default()
+        };
+        assert_eq!("aarch64", arch(&request).unwrap());
+    }
+}
@@ -0,0 +1,29 @@
+//! SapMachine.
+//!
+//! This module contains the implementation to query java packages from SAP (SapMachine).
+
+// https://github.com/SAP/SapMachine/releases
+
+#[doc(hidden)]
+mod api;
+
+use crate::provider::*;
+use crate::vendor::*;
+
+// The GitHub repository hosting the releases.
+#[doc(hidden)]
+const REPOSITORY: &str = "SAP/SapMachine";
+
+/// The [`VendorProvider`] for SAP (SapMachine).
+#[derive(Debug)]
+pub(crate) struct SapMachineProvider;
+
+impl VendorProvider for SapMachineProvider {
+    fn vendor(&self) -> Vendor {
+        Vendor::SapMachine
+    }
+
+    fn query(&self, request: &MetadataRequest) -> anyhow::Result<MetadataResponse> {
+        api::query(request)
+    }
+}
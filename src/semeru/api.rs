@@ -0,0 +1,129 @@
+use super::*;
+use crate::checksum::ChecksumAlgorithm;
+use crate::github;
+use anyhow::anyhow;
+use reqwest::blocking::Client;
+use semver::Version;
+
+// Query the API for all relevant data.
+pub(super) fn query(request: &MetadataRequest) -> anyhow::Result<MetadataResponse> {
+    if !request.jvm_impl.trim().is_empty() && request.jvm_impl() != "openj9" {
+        return Err(anyhow!("jvm implementation {} not available", request.jvm_impl()));
+    }
+
+    let client = Client::new();
+    let repository = repository(request);
+    let (release, version) = github::latest_release(&client, &repository, request, version_from_tag)?;
+
+    response_from_release(&client, request, &release, version)
+}
+
+// Build the response out of the matching asset (and its checksum asset) of the given release.
+fn response_from_release(client: &Client, request: &MetadataRequest, release: &serde_json::Value, version: Version) -> anyhow::Result<MetadataResponse> {
+    let Some(tag) = release["tag_name"].as_str() else {
+        return Err(anyhow!("field 'tag_name' not present in response"));
+    };
+
+    // url
+
+    let prefix = prefix(request)?;
+    let suffix = format!(".{}", request.archive_format());
+    let Some(asset) = github::find_asset(release, |n| n.starts_with(&prefix) && n.ends_with(&suffix)) else {
+        return Err(anyhow!("no package {prefix}*{suffix} found in release {tag}"));
+    };
+    let Some(url) = asset["browser_download_url"].as_str() else {
+        return Err(anyhow!("field 'browser_download_url' not present in response"));
+    };
+
+    // checksum
+
+    let name = asset["name"].as_str().unwrap_or_default();
+    let checksum_name = format!("{name}.sha256.txt");
+    let checksum_asset = github::find_asset(release, |n| n == checksum_name);
+    let checksum = github::checksum(client, asset, checksum_asset)?;
+
+    Ok(MetadataResponse {
+        checksum,
+        checksum_algorithm: ChecksumAlgorithm::Sha256,
+        companions: Vec::new(),
+        release_date: release["published_at"].as_str().and_then(parse_release_date),
+        url: url.to_string(),
+        version,
+    })
+}
+
+// Returns the GitHub repository (owner/repo) hosting the releases for the requested (major) version.
+fn repository(request: &MetadataRequest) -> String {
+    format!("{OWNER}/semeru{}-binaries", request.version())
+}
+
+// Returns the version from the given release tag (e.g. jdk-21.0.5+11_openj9-0.48.0 or jdk8u432-b06_openj9-0.48.0).
+fn version_from_tag(tag: &str) -> Option<Version> {
+    let tag = tag.split_once("_openj9").map_or(tag, |(tag, _)| tag);
+    if let Some(version) = tag.strip_prefix("jdk-") {
+        return parse_version(version).ok();
+    }
+
+    // legacy versioning scheme (e.g. 8u432-b06)
+    let (update, build) = tag.strip_prefix("jdk8u")?.split_once("-b")?;
+    let update: u64 = update.parse().ok()?;
+    let build: u64 = build.parse().ok()?;
+    parse_version(&format!("8.0.{update}+{build}")).ok()
+}
+
+// Returns the name prefix of the asset for the package (e.g. ibm-semeru-open-jdk_x64_linux_).
+fn prefix(request: &MetadataRequest) -> anyhow::Result<String> {
+    let package_type = request.package_type();
+    let arch = arch(request)?;
+    let os = os(request);
+    Ok(format!("ibm-semeru-open-{package_type}_{arch}_{os}_"))
+}
+
+// Returns the requested architecture for the package, named as used by the assets.
+fn arch(request: &MetadataRequest) -> anyhow::Result<&'static str> {
+    request.arch_name(Vendor::Semeru)
+}
+
+// Returns the requested operating system for the package, normalized for the assets.
+fn os(request: &MetadataRequest) -> String {
+    match request.os().as_str() {
+        "darwin" | "macos" | "macosx" => "mac".to_string(),
+        os => os.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn test_prefix() {
+        let request = MetadataRequest {
+            arch: "x86_64".to_string(),
+            os: "linux".to_string(),
+            package_type: "jre".to_string(),
+            version: "21".to_string(),
+            ..Default::default()
+        };
+        assert_eq!("ibm-semeru-open-jre_x64_linux_", prefix(&request).unwrap());
+        assert_eq!("ibmruntimes/semeru21-binaries", repository(&request));
+    }
+
+    #[test]
+    fn test_version_from_tag() {
+        assert_eq!(Some(Version::parse("21.0.5+11").unwrap()), version_from_tag("jdk-21.0.5+11_openj9-0.48.0"));
+        assert_eq!(Some(Version::parse("8.0.432+6").unwrap()), version_from_tag("jdk8u432-b06_openj9-0.48.0"));
+        assert_eq!(None, version_from_tag("latest"));
+    }
+
+    #[test]
+    fn test_hotspot_not_available() {
+        let request = MetadataRequest {
+            jvm_impl: "hotspot".to_string(),
+            ..Default::default()
+        };
+        assert!(query(&request).is_err());
+    }
+}
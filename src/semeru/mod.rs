@@ -0,0 +1,29 @@
+//! Semeru.
+//!
+//! This module contains the implementation to query java packages from IBM (Semeru).
+
+// https://github.com/ibmruntimes/semeru21-binaries/releases
+
+#[doc(hidden)]
+mod api;
+
+use crate::provider::*;
+use crate::vendor::*;
+
+// The owner of the GitHub repositories hosting the releases (one repository per major version).
+#[doc(hidden)]
+const OWNER: &str = "ibmruntimes";
+
+/// The [`VendorProvider`] for IBM (Semeru).
+#[derive(Debug)]
+pub(crate) struct SemeruProvider;
+
+impl VendorProvider for SemeruProvider {
+    fn vendor(&self) -> Vendor {
+        Vendor::Semeru
+    }
+
+    fn query(&self, request: &MetadataRequest) -> anyhow::Result<MetadataResponse> {
+        api::query(request)
+    }
+}
@@ -117,12 +117,31 @@ impl VarResolver for RustEnvVarResolver {
         match v {
             "JU_ARCH" => Ok(env::consts::ARCH.to_string()),
             "JU_FAMILY" => Ok(env::consts::FAMILY.to_string()),
+            "JU_LIBC" => Ok(host_libc().to_string()),
             "JU_OS" => Ok(env::consts::OS.to_string()),
             _ => Err(VarError::NotPresent(v.to_owned())),
         }
     }
 }
 
+/// Returns the C library (glibc or musl) of the host.
+///
+/// The C library is only detected on Linux (i.e. by looking for the musl dynamic loader), other operating systems
+/// don't have one to choose from and return an empty string.
+pub(crate) fn host_libc() -> &'static str {
+    if env::consts::OS != "linux" {
+        return "";
+    }
+    if cfg!(target_env = "musl") {
+        return "musl";
+    }
+
+    let musl = std::fs::read_dir("/lib")
+        .map(|entries| entries.flatten().any(|entry| entry.file_name().to_string_lossy().starts_with("ld-musl-")))
+        .unwrap_or_default();
+    if musl { "musl" } else { "glibc" }
+}
+
 /// [`VarResolver`] implementation for simple variables.
 #[derive(Debug)]
 pub(crate) struct SimpleVarResolver<'a> {
@@ -251,6 +270,13 @@ mod tests {
         assert_eq!(resolved, env::consts::FAMILY);
     }
 
+    #[test]
+    fn rust_env_var_resolver_libc() {
+        let resolver = RustEnvVarResolver;
+        let resolved = resolver.resolve_var("JU_LIBC").unwrap();
+        assert_eq!(resolved, host_libc());
+    }
+
     #[test]
     fn rust_env_var_resolver_os() {
         let resolver = RustEnvVarResolver;
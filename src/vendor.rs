@@ -12,6 +12,16 @@ const AZUL_ID: &str = "azul";
 #[doc(hidden)]
 const AZUL_NAME: &str = "Azul";
 
+// The id for Amazon (Corretto) as vendor.
+#[cfg(feature = "corretto")]
+#[doc(hidden)]
+const CORRETTO_ID: &str = "corretto";
+
+// The name for Amazon (Corretto) as vendor.
+#[cfg(feature = "corretto")]
+#[doc(hidden)]
+const CORRETTO_NAME: &str = "Amazon (Corretto)";
+
 // The id for Eclipse as vendor.
 #[cfg(feature = "eclipse")]
 #[doc(hidden)]
@@ -22,15 +32,122 @@ const ECLIPSE_ID: &str = "eclipse";
 #[doc(hidden)]
 const ECLIPSE_NAME: &str = "Eclipse";
 
+// The id for BellSoft (Liberica) as vendor.
+#[cfg(feature = "liberica")]
+#[doc(hidden)]
+const LIBERICA_ID: &str = "liberica";
+
+// The name for BellSoft (Liberica) as vendor.
+#[cfg(feature = "liberica")]
+#[doc(hidden)]
+const LIBERICA_NAME: &str = "BellSoft (Liberica)";
+
+// The id for Microsoft as vendor.
+#[cfg(feature = "microsoft")]
+#[doc(hidden)]
+const MICROSOFT_ID: &str = "microsoft";
+
+// The name for Microsoft as vendor.
+#[cfg(feature = "microsoft")]
+#[doc(hidden)]
+const MICROSOFT_NAME: &str = "Microsoft";
+
+// The id for SAP (SapMachine) as vendor.
+#[cfg(feature = "sapmachine")]
+#[doc(hidden)]
+const SAPMACHINE_ID: &str = "sapmachine";
+
+// The name for SAP (SapMachine) as vendor.
+#[cfg(feature = "sapmachine")]
+#[doc(hidden)]
+const SAPMACHINE_NAME: &str = "SAP (SapMachine)";
+
+// The id for IBM (Semeru) as vendor.
+#[cfg(feature = "semeru")]
+#[doc(hidden)]
+const SEMERU_ID: &str = "semeru";
+
+// The name for IBM (Semeru) as vendor.
+#[cfg(feature = "semeru")]
+#[doc(hidden)]
+const SEMERU_NAME: &str = "IBM (Semeru)";
+
+// The id for foojay (Disco API) as vendor.
+#[cfg(feature = "disco")]
+#[doc(hidden)]
+const DISCO_ID: &str = "disco";
+
+// The name for foojay (Disco API) as vendor.
+#[cfg(feature = "disco")]
+#[doc(hidden)]
+const DISCO_NAME: &str = "foojay (Disco API)";
+
+// The id for Manifest as vendor.
+#[cfg(feature = "manifest")]
+#[doc(hidden)]
+const MANIFEST_ID: &str = "manifest";
+
+// The name for Manifest as vendor.
+#[cfg(feature = "manifest")]
+#[doc(hidden)]
+const MANIFEST_NAME: &str = "Manifest";
+
+// The id for Direct as vendor.
+#[cfg(feature = "direct")]
+#[doc(hidden)]
+const DIRECT_ID: &str = "direct";
+
+// The name for Direct as vendor.
+#[cfg(feature = "direct")]
+#[doc(hidden)]
+const DIRECT_NAME: &str = "Direct";
+
+// The id for GitHub Releases as vendor.
+#[cfg(feature = "github-release")]
+#[doc(hidden)]
+const GITHUB_RELEASE_ID: &str = "github-release";
+
+// The name for GitHub Releases as vendor.
+#[cfg(feature = "github-release")]
+#[doc(hidden)]
+const GITHUB_RELEASE_NAME: &str = "GitHub Releases";
+
 /// Enumeration of supported vendors.
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub(crate) enum Vendor {
     #[cfg(feature = "azul")]
     /// Azul
     Azul,
+    #[cfg(feature = "corretto")]
+    /// Amazon (Corretto)
+    Corretto,
+    #[cfg(feature = "direct")]
+    /// Direct
+    Direct,
+    #[cfg(feature = "disco")]
+    /// foojay (Disco API)
+    Disco,
     #[cfg(feature = "eclipse")]
     /// Eclipse
     Eclipse,
+    #[cfg(feature = "github-release")]
+    /// GitHub Releases
+    GithubRelease,
+    #[cfg(feature = "liberica")]
+    /// BellSoft (Liberica)
+    Liberica,
+    #[cfg(feature = "manifest")]
+    /// Manifest
+    Manifest,
+    #[cfg(feature = "microsoft")]
+    /// Microsoft
+    Microsoft,
+    #[cfg(feature = "sapmachine")]
+    /// SAP (SapMachine)
+    SapMachine,
+    #[cfg(feature = "semeru")]
+    /// IBM (Semeru)
+    Semeru,
 }
 
 impl Vendor {
@@ -39,8 +156,26 @@ impl Vendor {
         match self {
             #[cfg(feature = "azul")]
             Self::Azul => AZUL_ID,
+            #[cfg(feature = "corretto")]
+            Self::Corretto => CORRETTO_ID,
+            #[cfg(feature = "direct")]
+            Self::Direct => DIRECT_ID,
+            #[cfg(feature = "disco")]
+            Self::Disco => DISCO_ID,
             #[cfg(feature = "eclipse")]
             Self::Eclipse => ECLIPSE_ID,
+            #[cfg(feature = "github-release")]
+            Self::GithubRelease => GITHUB_RELEASE_ID,
+            #[cfg(feature = "liberica")]
+            Self::Liberica => LIBERICA_ID,
+            #[cfg(feature = "manifest")]
+            Self::Manifest => MANIFEST_ID,
+            #[cfg(feature = "microsoft")]
+            Self::Microsoft => MICROSOFT_ID,
+            #[cfg(feature = "sapmachine")]
+            Self::SapMachine => SAPMACHINE_ID,
+            #[cfg(feature = "semeru")]
+            Self::Semeru => SEMERU_ID,
             #[expect(unreachable_patterns)]
             _ => unreachable!(),
         }
@@ -51,8 +186,201 @@ impl Vendor {
         match self {
             #[cfg(feature = "azul")]
             Self::Azul => AZUL_NAME,
+            #[cfg(feature = "corretto")]
+            Self::Corretto => CORRETTO_NAME,
+            #[cfg(feature = "direct")]
+            Self::Direct => DIRECT_NAME,
+            #[cfg(feature = "disco")]
+            Self::Disco => DISCO_NAME,
             #[cfg(feature = "eclipse")]
             Self::Eclipse => ECLIPSE_NAME,
+            #[cfg(feature = "github-release")]
+            Self::GithubRelease => GITHUB_RELEASE_NAME,
+            #[cfg(feature = "liberica")]
+            Self::Liberica => LIBERICA_NAME,
+            #[cfg(feature = "manifest")]
+            Self::Manifest => MANIFEST_NAME,
+            #[cfg(feature = "microsoft")]
+            Self::Microsoft => MICROSOFT_NAME,
+            #[cfg(feature = "sapmachine")]
+            Self::SapMachine => SAPMACHINE_NAME,
+            #[cfg(feature = "semeru")]
+            Self::Semeru => SEMERU_NAME,
+            #[expect(unreachable_patterns)]
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns the vendor for the given implementor (as found in the release file of a JDK), if known.
+    pub(crate) fn from_implementor(implementor: &str) -> Option<Self> {
+        let implementors: &[(&str, Self)] = &[
+            #[cfg(feature = "azul")]
+            ("azul", Self::Azul),
+            #[cfg(feature = "corretto")]
+            ("amazon", Self::Corretto),
+            #[cfg(feature = "eclipse")]
+            ("adoptium", Self::Eclipse),
+            #[cfg(feature = "liberica")]
+            ("bellsoft", Self::Liberica),
+            #[cfg(feature = "microsoft")]
+            ("microsoft", Self::Microsoft),
+            #[cfg(feature = "sapmachine")]
+            ("sap se", Self::SapMachine),
+            #[cfg(feature = "semeru")]
+            ("ibm", Self::Semeru),
+            #[cfg(feature = "semeru")]
+            ("international business machines", Self::Semeru),
+        ];
+        let implementor = implementor.trim().to_lowercase();
+        implementors.iter().find(|(name, _)| implementor.contains(name)).map(|(_, vendor)| *vendor)
+    }
+
+    /// Whether the vendor provides builds of arbitrary implementors (e.g. the Disco API or a manifest).
+    pub(crate) fn is_aggregator(&self) -> bool {
+        match self {
+            #[cfg(feature = "azul")]
+            Self::Azul => false,
+            #[cfg(feature = "corretto")]
+            Self::Corretto => false,
+            #[cfg(feature = "direct")]
+            Self::Direct => true,
+            #[cfg(feature = "disco")]
+            Self::Disco => true,
+            #[cfg(feature = "eclipse")]
+            Self::Eclipse => false,
+            #[cfg(feature = "github-release")]
+            Self::GithubRelease => true,
+            #[cfg(feature = "liberica")]
+            Self::Liberica => false,
+            #[cfg(feature = "manifest")]
+            Self::Manifest => true,
+            #[cfg(feature = "microsoft")]
+            Self::Microsoft => false,
+            #[cfg(feature = "sapmachine")]
+            Self::SapMachine => false,
+            #[cfg(feature = "semeru")]
+            Self::Semeru => false,
+            #[expect(unreachable_patterns)]
+            _ => unreachable!(),
+        }
+    }
+
+    /// Whether the build is pinned by the configuration (e.g. URL and checksum), so it replaces the installed build
+    /// whenever the configuration changes, regardless of their versions.
+    pub(crate) fn is_pinned(&self) -> bool {
+        match self {
+            #[cfg(feature = "azul")]
+            Self::Azul => false,
+            #[cfg(feature = "corretto")]
+            Self::Corretto => false,
+            #[cfg(feature = "direct")]
+            Self::Direct => true,
+            #[cfg(feature = "disco")]
+            Self::Disco => false,
+            #[cfg(feature = "eclipse")]
+            Self::Eclipse => false,
+            #[cfg(feature = "github-release")]
+            Self::GithubRelease => false,
+            #[cfg(feature = "liberica")]
+            Self::Liberica => false,
+            #[cfg(feature = "manifest")]
+            Self::Manifest => false,
+            #[cfg(feature = "microsoft")]
+            Self::Microsoft => false,
+            #[cfg(feature = "sapmachine")]
+            Self::SapMachine => false,
+            #[cfg(feature = "semeru")]
+            Self::Semeru => false,
+            #[expect(unreachable_patterns)]
+            _ => unreachable!(),
+        }
+    }
+
+    /// Whether the vendor provides builds for the musl C library (e.g. for Alpine).
+    pub(crate) fn provides_musl(&self) -> bool {
+        match self {
+            #[cfg(feature = "azul")]
+            Self::Azul => true,
+            #[cfg(feature = "corretto")]
+            Self::Corretto => false,
+            #[cfg(feature = "direct")]
+            Self::Direct => true,
+            #[cfg(feature = "disco")]
+            Self::Disco => true,
+            #[cfg(feature = "eclipse")]
+            Self::Eclipse => true,
+            #[cfg(feature = "github-release")]
+            Self::GithubRelease => false,
+            #[cfg(feature = "liberica")]
+            Self::Liberica => false,
+            #[cfg(feature = "manifest")]
+            Self::Manifest => false,
+            #[cfg(feature = "microsoft")]
+            Self::Microsoft => false,
+            #[cfg(feature = "sapmachine")]
+            Self::SapMachine => false,
+            #[cfg(feature = "semeru")]
+            Self::Semeru => false,
+            #[expect(unreachable_patterns)]
+            _ => unreachable!(),
+        }
+    }
+
+    /// Whether the vendor provides the release date of its builds (e.g. to apply a min-age policy).
+    pub(crate) fn provides_release_date(&self) -> bool {
+        match self {
+            #[cfg(feature = "azul")]
+            Self::Azul => false,
+            #[cfg(feature = "corretto")]
+            Self::Corretto => false,
+            #[cfg(feature = "direct")]
+            Self::Direct => false,
+            #[cfg(feature = "disco")]
+            Self::Disco => false,
+            #[cfg(feature = "eclipse")]
+            Self::Eclipse => true,
+            #[cfg(feature = "github-release")]
+            Self::GithubRelease => true,
+            #[cfg(feature = "liberica")]
+            Self::Liberica => false,
+            #[cfg(feature = "manifest")]
+            Self::Manifest => true,
+            #[cfg(feature = "microsoft")]
+            Self::Microsoft => false,
+            #[cfg(feature = "sapmachine")]
+            Self::SapMachine => true,
+            #[cfg(feature = "semeru")]
+            Self::Semeru => true,
+            #[expect(unreachable_patterns)]
+            _ => unreachable!(),
+        }
+    }
+
+    /// Whether the vendor provides builds bundled with JavaFX (aggregators list whatever is available).
+    pub(crate) fn provides_javafx(&self) -> bool {
+        match self {
+            #[cfg(feature = "azul")]
+            Self::Azul => true,
+            #[cfg(feature = "corretto")]
+            Self::Corretto => false,
+            #[cfg(feature = "direct")]
+            Self::Direct => true,
+            #[cfg(feature = "disco")]
+            Self::Disco => true,
+            #[cfg(feature = "eclipse")]
+            Self::Eclipse => false,
+            #[cfg(feature = "github-release")]
+            Self::GithubRelease => true,
+            #[cfg(feature = "liberica")]
+            Self::Liberica => true,
+            #[cfg(feature = "manifest")]
+            Self::Manifest => true,
+            #[cfg(feature = "microsoft")]
+            Self::Microsoft => false,
+            #[cfg(feature = "sapmachine")]
+            Self::SapMachine => false,
+            #[cfg(feature = "semeru")]
+            Self::Semeru => false,
             #[expect(unreachable_patterns)]
             _ => unreachable!(),
         }
@@ -73,8 +401,26 @@ impl TryFrom<&str> for Vendor {
         match value.as_str() {
             #[cfg(feature = "azul")]
             AZUL_ID => Ok(Self::Azul),
+            #[cfg(feature = "corretto")]
+            CORRETTO_ID => Ok(Self::Corretto),
+            #[cfg(feature = "direct")]
+            DIRECT_ID => Ok(Self::Direct),
+            #[cfg(feature = "disco")]
+            DISCO_ID => Ok(Self::Disco),
             #[cfg(feature = "eclipse")]
             ECLIPSE_ID => Ok(Self::Eclipse),
+            #[cfg(feature = "github-release")]
+            GITHUB_RELEASE_ID => Ok(Self::GithubRelease),
+            #[cfg(feature = "liberica")]
+            LIBERICA_ID => Ok(Self::Liberica),
+            #[cfg(feature = "manifest")]
+            MANIFEST_ID => Ok(Self::Manifest),
+            #[cfg(feature = "microsoft")]
+            MICROSOFT_ID => Ok(Self::Microsoft),
+            #[cfg(feature = "sapmachine")]
+            SAPMACHINE_ID => Ok(Self::SapMachine),
+            #[cfg(feature = "semeru")]
+            SEMERU_ID => Ok(Self::Semeru),
             _ => Err("unsupported vendor"),
         }
     }
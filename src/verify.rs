@@ -0,0 +1,255 @@
+//! Verification.
+//!
+//! This module contains the verification of a freshly unpacked java package by running it.
+
+use crate::arch::Arch;
+use crate::config::SmokeTestConfig;
+use crate::release::Release;
+use crate::vars::*;
+use anyhow::anyhow;
+use semver::Version;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::{Command, Output};
+use std::rc::Rc;
+use tracing::{debug, trace};
+
+// Name of the java executable.
+#[cfg(not(windows))]
+#[doc(hidden)]
+const JAVA_EXE: &str = "java";
+
+// Name of the java executable.
+#[cfg(windows)]
+#[doc(hidden)]
+const JAVA_EXE: &str = "java.exe";
+
+/// Variable (and environment variable) holding the java home of the build to verify.
+pub(crate) const ENV_JU_JAVA_HOME: &str = "JU_JAVA_HOME";
+
+/// Struct to hold the expectations (and the optional smoke test) to verify a java package against.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Verification {
+    arch: String,
+    smoke_test: Option<SmokeTest>,
+    version: Option<Version>,
+}
+
+/// Struct to hold the smoke-test command.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SmokeTest {
+    // The arguments for the executable.
+    args: Vec<String>,
+    // The working directory for the executable.
+    directory: Option<String>,
+    // The path to the executable.
+    path: String,
+}
+
+impl SmokeTest {
+    /// Creates a new `SmokeTest` out of the given `SmokeTestConfig`.
+    pub(crate) fn from_config(config: &SmokeTestConfig) -> Self {
+        Self {
+            args: config.args.clone(),
+            directory: config.directory.clone(),
+            path: config.path.clone(),
+        }
+    }
+}
+
+impl Verification {
+    /// Creates a new `Verification` expecting the given architecture.
+    pub(crate) fn new(arch: impl Into<String>) -> Self {
+        Self {
+            arch: arch.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the smoke-test command run after the version has been verified.
+    pub(crate) fn smoke_test(&mut self, smoke_test: Option<SmokeTest>) -> &mut Self {
+        self.smoke_test = smoke_test;
+
+        self
+    }
+
+    /// Sets the expected version.
+    pub(crate) fn version(&mut self, version: Version) -> &mut Self {
+        self.version = Some(version);
+
+        self
+    }
+
+    /// Verifies the java package in the given directory by running it (and the smoke test).
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub(crate) fn verify(&self, java_home: &Path) -> anyhow::Result<()> {
+        // only builds for the architecture of this host can be run
+        let expected = Arch::try_from(self.arch.as_str()).ok();
+        if expected != Arch::host() {
+            debug!(arch = self.arch, "skipping verification of build for foreign architecture");
+            return Ok(());
+        }
+
+        let java = java_home.join("bin").join(JAVA_EXE);
+        let output = Command::new(&java).args(["-XshowSettings:properties", "-version"]).output();
+        let output = output.map_err(|err| anyhow!("failed to run {}: {err}", java.display()))?;
+        if !output.status.success() {
+            return Err(anyhow!("failed to run {} ({}): {}", java.display(), output.status, stderr(&output)));
+        }
+        let properties = parse_properties(&stderr(&output));
+        trace!(?properties);
+
+        // version
+        if let Some(ref expected) = self.version {
+            let release = Release {
+                java_runtime_version: properties.get("java.runtime.version").cloned().unwrap_or_default(),
+                java_version: properties.get("java.version").cloned().unwrap_or_default(),
+                ..Default::default()
+            };
+            let actual = release.version()?;
+            if (actual.major, actual.minor, actual.patch) != (expected.major, expected.minor, expected.patch) {
+                return Err(anyhow!("build reports version {actual}, but version {expected} was expected"));
+            }
+        }
+
+        // architecture
+        let Some(arch) = properties.get("os.arch") else {
+            return Err(anyhow!("build reports no architecture"));
+        };
+        if Arch::try_from(arch.as_str()).ok() != expected {
+            return Err(anyhow!("build reports architecture {arch}, but architecture {} was expected", self.arch));
+        }
+
+        if let Some(ref smoke_test) = self.smoke_test {
+            run_smoke_test(smoke_test, java_home)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Runs the smoke-test command against the given java home.
+#[doc(hidden)]
+fn run_smoke_test(smoke_test: &SmokeTest, java_home: &Path) -> anyhow::Result<()> {
+    let java_home_str = java_home.to_string_lossy();
+
+    // setup variable resolver(s)
+    let mut simple_var_resolver = SimpleVarResolver::new();
+    simple_var_resolver.insert(ENV_JU_JAVA_HOME, java_home_str.to_string());
+    let env_var_resolver = PrefixedVarResolver::new("env.", Rc::new(OsEnvVarResolver));
+    let var_resolvers: Vec<Rc<dyn VarResolver>> = vec![Rc::new(simple_var_resolver), Rc::new(env_var_resolver)];
+    let var_expander = VarExpander::new(var_resolvers);
+
+    // setup command
+    let path = var_expander.expand(&smoke_test.path)?;
+    let mut command = Command::new(path.as_ref());
+    for arg in &smoke_test.args {
+        command.arg(var_expander.expand(arg)?.as_ref());
+    }
+    if let Some(ref directory) = smoke_test.directory {
+        command.current_dir(var_expander.expand(directory)?.as_ref());
+    }
+    command.env("JAVA_HOME", java_home_str.as_ref());
+    command.env(ENV_JU_JAVA_HOME, java_home_str.as_ref());
+    trace!(?command);
+
+    let output = command.output().map_err(|err| anyhow!("failed to run smoke test {path}: {err}"))?;
+    if !output.status.success() {
+        return Err(anyhow!("smoke test {path} failed ({}): {}", output.status, stderr(&output)));
+    }
+
+    Ok(())
+}
+
+// Returns the (trimmed) standard error of the given output.
+#[doc(hidden)]
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).trim().to_string()
+}
+
+// Parses the properties (lines like `    key = value`) printed by `-XshowSettings:properties`.
+#[doc(hidden)]
+fn parse_properties(output: &str) -> HashMap<String, String> {
+    let mut properties = HashMap::new();
+    for line in output.lines() {
+        if let Some((key, value)) = line.trim().split_once(" = ") {
+            properties.insert(key.trim().to_string(), value.trim().to_string());
+        }
+    }
+
+    // fall back to the quoted version of the version banner (e.g. openjdk version "21.0.5" 2024-10-15)
+    if !properties.contains_key("java.version")
+        && let Some(version) = output.lines().find(|line| line.contains(" version \"")).and_then(|line| line.split('"').nth(1))
+    {
+        properties.insert("java.version".to_string(), version.to_string());
+    }
+
+    properties
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::env;
+    use test_log::test;
+
+    const OUTPUT: &str = r#"Property settings:
+    file.separator = /
+    java.library.path = /usr/java/packages/lib
+        /usr/lib64
+    java.runtime.version = 21.0.5+11-LTS
+    java.version = 21.0.5
+    os.arch = amd64
+    os.name = Linux
+
+openjdk version "21.0.5" 2024-10-15 LTS
+OpenJDK Runtime Environment Temurin-21.0.5+11 (build 21.0.5+11-LTS)
+"#;
+
+    #[test]
+    fn properties() {
+        let properties = parse_properties(OUTPUT);
+        assert_eq!(Some(&"21.0.5+11-LTS".to_string()), properties.get("java.runtime.version"));
+        assert_eq!(Some(&"amd64".to_string()), properties.get("os.arch"));
+        assert_eq!(Some(&"/usr/java/packages/lib".to_string()), properties.get("java.library.path"));
+    }
+
+    #[test]
+    fn properties_from_banner() {
+        let properties = parse_properties("java version \"1.8.0_432\"\nJava(TM) SE Runtime Environment");
+        assert_eq!(Some(&"1.8.0_432".to_string()), properties.get("java.version"));
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn verify_fake_java() {
+        use std::fs;
+        use std::os::unix::fs::PermissionsExt;
+
+        let tempdir = tempfile::tempdir().unwrap();
+        let java = tempdir.path().join("bin").join("java");
+        fs::create_dir_all(java.parent().unwrap()).unwrap();
+        let script = format!("#!/bin/sh\necho '    java.version = 21.0.5\n    os.arch = {}' >&2\n", env::consts::ARCH);
+        fs::write(&java, script).unwrap();
+        fs::set_permissions(&java, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut verification = Verification::new(env::consts::ARCH);
+        assert!(verification.version(Version::new(21, 0, 5)).verify(tempdir.path()).is_ok());
+        assert!(verification.version(Version::new(21, 0, 6)).verify(tempdir.path()).is_err());
+
+        // smoke test
+        let smoke_test = SmokeTest {
+            args: vec!["-c".to_string(), "test -x ${JU_JAVA_HOME}/bin/java".to_string()],
+            directory: None,
+            path: "sh".to_string(),
+        };
+        verification.version(Version::new(21, 0, 5)).smoke_test(Some(smoke_test.clone()));
+        assert!(verification.verify(tempdir.path()).is_ok());
+        let smoke_test = SmokeTest {
+            args: vec!["-c".to_string(), "exit 1".to_string()],
+            ..smoke_test
+        };
+        assert!(verification.smoke_test(Some(smoke_test)).verify(tempdir.path()).is_err());
+    }
+}